@@ -5,65 +5,104 @@
 //
 // https://adventofcode.com/2022/day/1
 
+use std::str::FromStr;
+
+use anyhow::Context;
+use aoc_common::generate::Rng;
+
 #[derive(Debug, Clone, Copy)]
 struct Elf {
-    id: u32,
     calories: u32,
 }
 
-fn part1(elves: Vec<Elf>) -> std::io::Result<()> {
-    // GOAL: Find the Elf carrying the most Calories. How many total Calories is that Elf carrying?
-    let Some(elf) = elves.iter().max_by(|a, b| a.calories.cmp(&b.calories)) else {
-        println!("No elves found!");
-        return Ok(());
-    };
+impl FromStr for Elf {
+    type Err = anyhow::Error;
 
-    println!("Part 1: {}", elf.calories);
-    Ok(())
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // each line is the calories of a single item the elf is carrying
+        let calories = s
+            .lines()
+            .map(|line| {
+                line.parse::<u32>()
+                    .with_context(|| format!("invalid calories `{line}`"))
+            })
+            .sum::<anyhow::Result<u32>>()?;
+
+        Ok(Elf { calories })
+    }
 }
 
-fn part2(mut elves: Vec<Elf>) -> std::io::Result<()> {
-    // GOAL: Find the top three Elves carrying the most Calories. How many Calories are those Elves carrying in total?
-    elves.sort_by(|a, b| b.calories.cmp(&a.calories));
-    let top_elves = &elves[0..3];
-    let total_calories: u32 = top_elves.iter().map(|elf| elf.calories).sum();
-    println!(
-        "Part 2: {}",
-        total_calories
-    );
-
-    Ok(())
+#[aoc_common::aoc(day = 1, parse)]
+fn elves(input: &str) -> anyhow::Result<Vec<Elf>> {
+    // the inventory of each elf is separated by an empty line
+    input
+        .split("\n\n")
+        .enumerate()
+        .map(|(i, elf)| {
+            elf.parse()
+                .with_context(|| format!("failed to parse elf {}", i + 1))
+        })
+        .collect()
 }
 
-fn main() -> std::io::Result<()> {
-    // get lines from stdin
-    let lines: Vec<String> = std::io::stdin()
-        .lines()
-        .filter_map(|line| line.ok())
-        .collect();
-
-    // parse lines into numbers
-    let mut elves: Vec<Elf> = Vec::new();
-
-    let mut elf = Elf { id: 0, calories: 0 };
-    // loop over all lines
-    for line in lines {
-        // if the line is not empty, parse the line for the current elf
-        if let Ok(calories) = line.parse::<u32>() {
-            elf.calories += calories;
-        } else {
-            // otherwise, add the elf to the list and start a new elf
-            let i = elf.id;
-            elves.push(elf);
-            elf = Elf {
-                id: i + 1,
-                calories: 0,
-            };
-        }
+#[aoc_common::aoc(day = 1, part = 1)]
+fn part1(elves: &[Elf]) -> anyhow::Result<u32> {
+    // GOAL: Find the Elf carrying the most Calories. How many total
+    // Calories is that Elf carrying?
+    elves
+        .iter()
+        .map(|elf| elf.calories)
+        .max()
+        .context("no elves found")
+}
+
+#[aoc_common::aoc(day = 1, part = 2, generator = generate)]
+fn part2(elves: &[Elf]) -> anyhow::Result<u32> {
+    // GOAL: Find the top three Elves carrying the most Calories. How many
+    // Calories are those Elves carrying in total?
+    let mut calories = elves.iter().map(|elf| elf.calories).collect::<Vec<_>>();
+    if calories.len() < 3 {
+        anyhow::bail!("fewer than three elves found");
     }
 
-    part1(elves.clone())?;
-    part2(elves.clone())?;
+    calories.sort_by_key(|&calories| std::cmp::Reverse(calories));
+    Ok(calories[..3].iter().sum())
+}
 
-    Ok(())
+/// An inventory of `size` elves, or three if fewer, since part 2 needs them.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(3))
+        .map(|_| {
+            (0..rng.range(1..8))
+                .map(|_| rng.range(1_000..70_000).to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
 }
+
+#[aoc_common::aoc_example(part = 1, expected = 24000)]
+#[aoc_common::aoc_example(part = 2, expected = 45000)]
+const EXAMPLE: &str = r"
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+    ";
+
+#[aoc_common::aoc_example(
+    part = 2,
+    expected = aoc_common::Fails("fewer than three elves")
+)]
+const TWO_ELVES: &str = "1000\n\n2000";
@@ -16,21 +16,45 @@
 // Shape pts -> Rock = 1, Paper = 2, Scissors = 3
 // Outcome pts -> Lose = 0, Draw = 3, Win = 6
 
+use std::str::FromStr;
+
+use aoc_common::{generate::Rng, Solution};
+
 #[derive(Debug, Clone, Copy)]
 struct Round {
-    opp: RPS,
-    res: RPS,
+    opp: Rps,
+    res: Rps,
 }
 
-#[derive(Debug, Clone, Copy)]
-enum RPS {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rps {
     Rock,
     Paper,
     Scissors,
 }
 
-fn calc_round_score(opp: RPS, res: RPS) -> u32 {
-    use RPS::*;
+impl Rps {
+    /// The shape that this shape beats.
+    fn beats(self) -> Rps {
+        match self {
+            Rps::Rock => Rps::Scissors,
+            Rps::Paper => Rps::Rock,
+            Rps::Scissors => Rps::Paper,
+        }
+    }
+
+    /// The shape that beats this shape.
+    fn beaten_by(self) -> Rps {
+        match self {
+            Rps::Rock => Rps::Paper,
+            Rps::Paper => Rps::Scissors,
+            Rps::Scissors => Rps::Rock,
+        }
+    }
+}
+
+fn calc_round_score(opp: Rps, res: Rps) -> u32 {
+    use Rps::*;
     let mut score = 0;
 
     // add shape points
@@ -53,85 +77,116 @@ fn calc_round_score(opp: RPS, res: RPS) -> u32 {
     score
 }
 
-fn calc_total_score(rounds: Vec<Round>) -> u32 {
-    rounds.iter()
-        .map(|r| calc_round_score(r.opp, r.res))
-        .sum()
+fn calc_total_score(rounds: impl Iterator<Item = Round>) -> u32 {
+    rounds.map(|r| calc_round_score(r.opp, r.res)).sum()
 }
 
-fn part1(lines: Vec<String>) {
-    // each round is a single line
-    // -> map each line to opponent RPS and response RPS
-    let rounds: Vec<Round> = lines.iter()
-        .map(|l| l.split(" ").collect::<Vec<&str>>())
-        .map(|v| {
-            let opp = match v[0] {
-                "A" => RPS::Rock,
-                "B" => RPS::Paper,
-                "C" => RPS::Scissors,
-                _ => panic!("invalid input")
-            };
-            let res = match v[1] {
-                "X" => RPS::Rock,
-                "Y" => RPS::Paper,
-                "Z" => RPS::Scissors,
-                _ => panic!("invalid input")
-            };
-            Round { opp, res }
-        })
-        .collect();
+/// A line of the strategy guide. The meaning of the second column depends on
+/// the part.
+#[derive(Debug, Clone, Copy)]
+struct Line {
+    opp: Rps,
+    column: Column,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Column {
+    X,
+    Y,
+    Z,
+}
+
+impl FromStr for Line {
+    type Err = anyhow::Error;
 
-    // GOAL: What would your total score be if everything goes exactly according to your strategy guide?
-    let score = calc_total_score(rounds);
-    println!("Part 1: {}", score);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((opp, column)) = s.split_once(' ') else {
+            anyhow::bail!("couldn't split on space");
+        };
+
+        let opp = match opp {
+            "A" => Rps::Rock,
+            "B" => Rps::Paper,
+            "C" => Rps::Scissors,
+            _ => anyhow::bail!("invalid opponent shape `{opp}`"),
+        };
+        let column = match column {
+            "X" => Column::X,
+            "Y" => Column::Y,
+            "Z" => Column::Z,
+            _ => anyhow::bail!("invalid second column `{column}`"),
+        };
+
+        Ok(Line { opp, column })
+    }
 }
 
-fn part2(lines: Vec<String>) {
-    use RPS::*;
-
-    // each round is a single line
-    // -> map each line to opponent RPS and response RPS
-    let rounds: Vec<Round> = lines.iter()
-        .map(|l| l.split(" ").collect::<Vec<&str>>())
-        .map(|v| {
-            let opp = match v[0] {
-                "A" => Rock,
-                "B" => Paper,
-                "C" => Scissors,
-                _ => panic!("invalid input")
+struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Vec<Line>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        // each round is a single line
+        aoc_common::parse_lines(input)
+    }
+
+    fn part1(lines: &Self::Parsed) -> anyhow::Result<u32> {
+        // the second column is our response shape
+        let rounds = lines.iter().map(|l| {
+            let res = match l.column {
+                Column::X => Rps::Rock,
+                Column::Y => Rps::Paper,
+                Column::Z => Rps::Scissors,
             };
-            // X = Lose, Y = Draw, Z = Win
-            let res = match v[1] {
-                "X" => match opp {
-                    Rock => Scissors,
-                    Paper => Rock,
-                    Scissors => Paper,
-                },
-                "Y" => opp, // draw means that the two shapes are equal
-                "Z" => match opp {
-                    Rock => Paper,
-                    Paper => Scissors,
-                    Scissors => Rock,
-                },
-                _ => panic!("invalid input")
+            Round { opp: l.opp, res }
+        });
+
+        // GOAL: What would your total score be if everything goes exactly
+        // according to your strategy guide?
+        Ok(calc_total_score(rounds))
+    }
+
+    fn part2(lines: &Self::Parsed) -> anyhow::Result<u32> {
+        // X = Lose, Y = Draw, Z = Win
+        let rounds = lines.iter().map(|l| {
+            let res = match l.column {
+                Column::X => l.opp.beats(),
+                // draw means that the two shapes are equal
+                Column::Y => l.opp,
+                Column::Z => l.opp.beaten_by(),
             };
-            Round { opp, res }
-        })
-        .collect();
+            Round { opp: l.opp, res }
+        });
+
+        // GOAL: Following the Elf's instructions for the second column, what
+        // would your total score be if everything goes exactly according to
+        // your strategy guide?
+        Ok(calc_total_score(rounds))
+    }
 
-        // GOAL: Following the Elf's instructions for the second column, what would your total score be if everything goes exactly according to your strategy guide?
-    let score = calc_total_score(rounds);
-    println!("Part 2: {}", score);
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(
+            (0..size)
+                .map(|_| {
+                    let opp = rng.pick(&['A', 'B', 'C']);
+                    format!("{opp} {}\n", rng.pick(&['X', 'Y', 'Z']))
+                })
+                .collect(),
+        )
+    }
 }
 
-fn main() -> std::io::Result<()> {
-    // get lines from stdin
-    let lines: Vec<String> = std::io::stdin()
-        .lines()
-        .filter_map(|line| line.ok())
-        .collect();
-    
-    part1(lines.clone());
-    part2(lines.clone());
-    Ok(())
+aoc_common::setup! {
+    "day2",
+    Day2,
+    Example: r"
+A Y
+B X
+C Z
+    ",
+    Part1: 15,
+    Part2: 12,
 }
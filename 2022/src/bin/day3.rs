@@ -7,64 +7,16 @@
 
 // Both compartments have the SAME number of items
 
-use std::io;
+use std::str::FromStr;
 
-// VERY UGLY!
-fn char_to_prio(c: char) -> u32 {
+use anyhow::Context;
+use aoc_common::{generate::Rng, Solution};
+
+fn char_to_prio(c: char) -> anyhow::Result<u32> {
     match c {
-        'a' => 1,
-        'b' => 2,
-        'c' => 3,
-        'd' => 4,
-        'e' => 5,
-        'f' => 6,
-        'g' => 7,
-        'h' => 8,
-        'i' => 9,
-        'j' => 10,
-        'k' => 11,
-        'l' => 12,
-        'm' => 13,
-        'n' => 14,
-        'o' => 15,
-        'p' => 16,
-        'q' => 17,
-        'r' => 18,
-        's' => 19,
-        't' => 20,
-        'u' => 21,
-        'v' => 22,
-        'w' => 23,
-        'x' => 24,
-        'y' => 25,
-        'z' => 26,
-        'A' => 27,
-        'B' => 28,
-        'C' => 29,
-        'D' => 30,
-        'E' => 31,
-        'F' => 32,
-        'G' => 33,
-        'H' => 34,
-        'I' => 35,
-        'J' => 36,
-        'K' => 37,
-        'L' => 38,
-        'M' => 39,
-        'N' => 40,
-        'O' => 41,
-        'P' => 42,
-        'Q' => 43,
-        'R' => 44,
-        'S' => 45,
-        'T' => 46,
-        'U' => 47,
-        'V' => 48,
-        'W' => 49,
-        'X' => 50,
-        'Y' => 51,
-        'Z' => 52,
-        _ => panic!("invalid char"),
+        'a'..='z' => Ok(c as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Ok(c as u32 - 'A' as u32 + 27),
+        _ => anyhow::bail!("invalid item `{c}`"),
     }
 }
 
@@ -74,102 +26,131 @@ struct Rucksack {
     right: Vec<char>,
 }
 
-fn find_dupe(rs: &Rucksack) -> char {
-    for c in &rs.left {
-        for d in &rs.right {
-            if c == d {
-                return *c;
-            }
-        }
+impl Rucksack {
+    /// Every item in the rucksack, from both compartments.
+    fn items(&self) -> impl Iterator<Item = &char> {
+        self.left.iter().chain(&self.right)
     }
+}
+
+impl FromStr for Rucksack {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.len().is_multiple_of(2) {
+            anyhow::bail!("compartments are not the same size");
+        }
 
-    panic!("no dupe found");
+        // compartments are a half of the full string
+        let (a, b) = s.split_at(s.len() / 2);
+        let left = a.chars().collect();
+        let right = b.chars().collect();
+
+        Ok(Rucksack { left, right })
+    }
 }
 
-fn part1(lines: Vec<String>) {
-    // create a list of rucksacks
-    // each rusksack has two compartments
-    // compartments are a half of the full string
-    let sacks: Vec<Rucksack> = lines
+fn find_dupe(rs: &Rucksack) -> anyhow::Result<char> {
+    rs.left
         .iter()
-        .map(|line| {
-            let (a, b) = line.split_at(line.len() / 2);
-            let left = a.chars().collect();
-            let right = b.chars().collect();
-            Rucksack { left, right }
-        })
-    .collect();
-
-    // sum the priorities of the duplicated items
-    // (items that are in both compartments of a rucksack)
-    let prio_sum: u32 = sacks.iter()
-        .map(|rs| find_dupe(&rs))
-        .map(|ch| char_to_prio(ch))
-        .sum();
-    println!("Part 1: {}", prio_sum);
+        .find(|c| rs.right.contains(c))
+        .copied()
+        .context("no item is in both compartments")
+}
+
+type Group = [Rucksack];
+
+fn find_dupe_multi(g: &Group) -> anyhow::Result<char> {
+    // the sacks' compartments don't matter for P2, so find the common letter
+    // between all items of every sack
+    let (first, rest) = g.split_first().context("empty group")?;
+
+    first
+        .items()
+        .find(|c| rest.iter().all(|rs| rs.items().any(|d| d == *c)))
+        .copied()
+        .context("no item is in every rucksack of the group")
 }
 
-type Group = [Rucksack; 3];
+struct Day3;
 
-fn find_dupe_multi(g: &Group) -> char {
-    // combine the sacks' compartments since they don't matter for P2
-    let mut groups: Vec<Vec<char>> = Vec::new();
-    for rs in g {
-        let mut v = Vec::new();
-        v.append(&mut rs.left.clone());
-        v.append(&mut rs.right.clone());
-        groups.push(v);
+impl Solution for Day3 {
+    type Parsed = Vec<Rucksack>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        // create a list of rucksacks
+        // each rusksack has two compartments
+        aoc_common::parse_lines(input)
     }
 
-    // find the common letter between all sacks
-    for a in &groups[0] {
-        for b in &groups[1] {
-            for c in &groups[2] {
-                if a == b && b == c {
-                    return *a;
-                }
-            }
+    fn part1(sacks: &Self::Parsed) -> anyhow::Result<u32> {
+        // sum the priorities of the duplicated items
+        // (items that are in both compartments of a rucksack)
+        sacks
+            .iter()
+            .map(|rs| find_dupe(rs).and_then(char_to_prio))
+            .sum()
+    }
+
+    fn part2(sacks: &Self::Parsed) -> anyhow::Result<u32> {
+        let (groups, rest) = sacks.as_chunks::<3>();
+        if !rest.is_empty() {
+            anyhow::bail!("rucksacks can't be split into groups of three");
         }
+
+        // sum the priorities of the badges
+        // (items that are in all three rucksacks of a group)
+        groups
+            .iter()
+            .map(|g| find_dupe_multi(g).and_then(char_to_prio))
+            .sum()
     }
 
-    panic!("no dupe found");
-}
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let items = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+        let mut input = String::new();
 
-fn part2(lines: Vec<String>) {
-    // create a list of rucksacks
-    // each rusksack has two compartments
-    // compartments are a half of the full string
-    let sacks: Vec<Rucksack> = lines
-        .iter()
-        .map(|line| {
-            let (a, b) = line.split_at(line.len() / 2);
-            let left = a.chars().collect();
-            let right = b.chars().collect();
-            Rucksack { left, right }
-        })
-    .collect();
-    let mut groups: Vec<Group> = Vec::new();
-    {
-        let mut i = 0;
-        while i < (sacks.len() - 2) {
-            groups.push([sacks[i].clone(), sacks[i+1].clone(), sacks[i+2].clone()]);
-            i += 3;
+        // the size is rounded up to whole groups of three rucksacks, which
+        // share a badge as well as having an item in both compartments
+        for _ in 0..size.div_ceil(3).max(1) {
+            let badge = *rng.pick(&items);
+
+            for _ in 0..3 {
+                let half = rng.range(8..17) as usize;
+                let mut compartment =
+                    || (0..half).map(|_| *rng.pick(&items)).collect::<Vec<_>>();
+                let mut left = compartment();
+                let mut right = compartment();
+
+                let dupe = *rng.pick(&items);
+                left[0] = dupe;
+                right[0] = dupe;
+                left[1] = badge;
+                rng.shuffle(&mut left);
+                rng.shuffle(&mut right);
+
+                input.extend(left.into_iter().chain(right));
+                input.push('\n');
+            }
         }
+
+        Some(input)
     }
-    
-    // sum the priorities of the duplicated items
-    // (items that are in both compartments of a rucksack)
-    let prio_sum: u32 = groups.iter()
-        .map(|rs| find_dupe_multi(rs))
-        .map(|ch| char_to_prio(ch))
-        .sum();
-    println!("Part 2: {}", prio_sum);
 }
 
-fn main() {
-    // get lines from stdin
-    let lines: Vec<String> = io::stdin().lines().filter_map(|line| line.ok()).collect();
-
-    part1(lines.clone());
-    part2(lines.clone());
+aoc_common::setup! {
+    "day3",
+    Day3,
+    Example: r"
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+    ",
+    Part1: 157,
+    Part2: 70,
 }
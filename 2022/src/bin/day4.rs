@@ -4,103 +4,92 @@
 // Day 4
 //
 // https://adventofcode.com/2022/day/4
-#![allow(unused)]
 
-type Range = std::ops::RangeInclusive<u32>;
+use aoc_common::{
+    generate::Rng,
+    interval::Interval,
+    parser::{self, lines, literal, unsigned, Parser},
+    Solution,
+};
 
-/// Does range A fully contain range B.
-fn range_contains(a: &Range, b: &Range) -> bool {
-    // a range full contains another when it contains
-    // both the start and end of the other range
-    a.contains(&b.start()) && a.contains(&b.end())
-}
-
-/// Does range A overlap range B at all?
-fn range_overlap(a: &Range, b: &Range) -> bool {
-    // overlap when any value within range A is contained in range B
-    for i in a.clone().into_iter() {
-        if b.contains(&i) {
-            return true;
-        }
-    }
-
-    false
+/// A range of sections, e.g. `2-4`.
+fn range<'a>() -> impl Parser<'a, Interval> {
+    unsigned()
+        .then_ignore(literal("-"))
+        .then(unsigned())
+        .try_map("a range that doesn't end before it starts", |(btm, top)| {
+            Interval::new(btm, top)
+        })
 }
 
 #[derive(Debug, Clone)]
-struct Pair(Range, Range);
+struct Pair(Interval, Interval);
 
 impl Pair {
     /// Does one range (in the pair) overlap the entire other range?
     fn does_fully_contain(&self) -> bool {
-        range_contains(&self.0, &self.1) || range_contains(&self.1, &self.0)
+        self.0.contains_interval(self.1) || self.1.contains_interval(self.0)
     }
 
     /// Do the two ranges overlap at all?
     fn does_overlap(&self) -> bool {
-        range_overlap(&self.0, &self.1)
+        self.0.overlaps(self.1)
     }
 }
 
-fn part1(lines: Vec<String>) -> usize {
-    lines.iter()
-        // we have: "#-#,#-#" -- we want: ["#-#", "#-#"]
-        .map(|line| line.split(",")
-             .map(|s| s.to_owned())
-             .collect::<Vec<String>>())
-        // we have: ["#-#", "#-#"] -- we want ("#-#", "#-#")
-        .map(|v| (v[0].clone(), v[1].clone()))
-        // we have: ("#-#", "#-#") -- we want: ((#, #), (#, #))
-        .map(|(a, b)| {
-            let spt_a = a.split("-").collect::<Vec<&str>>();
-            let spt_b = b.split("-").collect::<Vec<&str>>();
+struct Day4;
 
-            let a_btm = spt_a[0].parse::<u32>().unwrap();
-            let a_top = spt_a[1].parse::<u32>().unwrap();
-            let b_btm = spt_b[0].parse::<u32>().unwrap();
-            let b_top = spt_b[1].parse::<u32>().unwrap();
+impl Solution for Day4 {
+    type Parsed = Vec<Pair>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-            ((a_btm, a_top), (b_btm, b_top))
-        })
-        // we have: ((#, #), (#, #)) -- we want: Pair(#..=#, #..=#)
-        .map(|(a, b)| Pair(a.0..=a.1, b.0..=b.1))
-        // filter the pairs that don't have on range fully contained
-        .filter(|p| p.does_fully_contain())
-        .count()
-}
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        // we have: "#-#,#-#" -- we want: Pair(#..=#, #..=#)
+        let pair = range()
+            .then_ignore(literal(","))
+            .then(range())
+            .map(|(a, b)| Pair(a, b));
 
-fn part2(lines: Vec<String>) -> usize {
-    lines.iter()
-        // we have: "#-#,#-#" -- we want: ["#-#", "#-#"]
-        .map(|line| line.split(",")
-             .map(|s| s.to_owned())
-             .collect::<Vec<String>>())
-        // we have: ["#-#", "#-#"] -- we want ("#-#", "#-#")
-        .map(|v| (v[0].clone(), v[1].clone()))
-        // we have: ("#-#", "#-#") -- we want: ((#, #), (#, #))
-        .map(|(a, b)| {
-            let spt_a = a.split("-").collect::<Vec<&str>>();
-            let spt_b = b.split("-").collect::<Vec<&str>>();
+        Ok(parser::parse_all(lines(pair), input)?)
+    }
 
-            let a_btm = spt_a[0].parse::<u32>().unwrap();
-            let a_top = spt_a[1].parse::<u32>().unwrap();
-            let b_btm = spt_b[0].parse::<u32>().unwrap();
-            let b_top = spt_b[1].parse::<u32>().unwrap();
+    fn part1(pairs: &Self::Parsed) -> anyhow::Result<usize> {
+        // count the pairs that have one range fully contained
+        Ok(pairs.iter().filter(|p| p.does_fully_contain()).count())
+    }
 
-            ((a_btm, a_top), (b_btm, b_top))
-        })
-        // we have: ((#, #), (#, #)) -- we want: Pair(#..=#, #..=#)
-        .map(|(a, b)| Pair(a.0..=a.1, b.0..=b.1))
-        // filter the pairs that don't have on range fully contained
-        .filter(|p| p.does_overlap())
-        .count()
-}
+    fn part2(pairs: &Self::Parsed) -> anyhow::Result<usize> {
+        // count the pairs that overlap at all
+        Ok(pairs.iter().filter(|p| p.does_overlap()).count())
+    }
 
-fn main() {
-    let lines = std::io::stdin().lines()
-        .filter_map(|line| line.ok())
-        .collect::<Vec<String>>();
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        // sections are numbered up to 99, as in the real input
+        let mut range = || {
+            let start = rng.range(1..100);
+            format!("{start}-{}", rng.range(start..100))
+        };
+
+        Some(
+            (0..size)
+                .map(|_| format!("{},{}\n", range(), range()))
+                .collect(),
+        )
+    }
+}
 
-    println!("Part 1: {}", part1(lines.clone()));
-    println!("Part 2: {}", part2(lines.clone()));
+aoc_common::setup! {
+    "day4",
+    Day4,
+    Example: r"
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+    ",
+    Part1: 2,
+    Part2: 4,
 }
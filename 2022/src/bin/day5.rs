@@ -4,15 +4,11 @@
 // Day 5
 //
 // https://adventofcode.com/2022/day/5
-#![allow(unused)]
 
-use std::{str::FromStr, collections::VecDeque};
-use anyhow::bail;
+use std::str::FromStr;
 
-/// Converts the column number to a index within a row of characters.
-fn column_to_index(c: usize) -> usize {
-    4 * (c - 1) + 1
-}
+use anyhow::Context;
+use aoc_common::{generate::Rng, grid::Grid, numbers, Solution};
 
 type Crate = char;
 type Stack = Vec<Crate>;
@@ -26,28 +22,51 @@ struct Movement {
 }
 
 impl Movement {
-    /// Execute the movement on the given platform.
-    pub fn execute(&self, platform: &mut Platform) {
-        for i in 0..self.count {
-            let c = platform[self.from-1].pop().unwrap();
-            platform[self.to-1].push(c);
+    /// Take the crates being moved off the top of the `from` stack, leaving
+    /// the topmost crate last.
+    fn lift(&self, platform: &mut Platform) -> anyhow::Result<Vec<Crate>> {
+        let stack = platform
+            .get_mut(self.from - 1)
+            .with_context(|| format!("no stack {}", self.from))?;
+
+        if stack.len() < self.count {
+            anyhow::bail!(
+                "can't move {} crates from stack {} with {}",
+                self.count,
+                self.from,
+                stack.len()
+            );
         }
+
+        Ok(stack.split_off(stack.len() - self.count))
+    }
+
+    /// Put the crates onto the `to` stack, in order.
+    fn place(
+        &self,
+        platform: &mut Platform,
+        crates: impl IntoIterator<Item = Crate>,
+    ) -> anyhow::Result<()> {
+        platform
+            .get_mut(self.to - 1)
+            .with_context(|| format!("no stack {}", self.to))?
+            .extend(crates);
+
+        Ok(())
     }
 
     /// Execute the movement on the given platform.
-    /// -- Alternate: move multiple crates at once
-    pub fn execute_alt(&self, platform: &mut Platform) {
-        // this is achieved by popping all then pushing
-        // in reverse order
-        let mut crane: Vec<Crate> = Vec::with_capacity(self.count);
-        for i in 0..self.count {
-            let c = platform[self.from-1].pop().unwrap();
-            crane.push(c);
-        }
+    pub fn execute(&self, platform: &mut Platform) -> anyhow::Result<()> {
+        // moving one crate at a time reverses their order
+        let crane = self.lift(platform)?;
+        self.place(platform, crane.into_iter().rev())
+    }
 
-        for &c in crane.iter().rev() {
-            platform[self.to-1].push(c);
-        }
+    /// Execute the movement on the given platform.
+    /// -- Alternate: move multiple crates at once
+    pub fn execute_alt(&self, platform: &mut Platform) -> anyhow::Result<()> {
+        let crane = self.lift(platform)?;
+        self.place(platform, crane)
     }
 }
 
@@ -57,83 +76,199 @@ impl FromStr for Movement {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // we only care about numbers
         // the order of which make up the movement
-        let r = s.split(" ").collect::<Vec<_>>().iter()
-            .filter_map(|c| c.parse::<usize>().ok())
-            .collect::<Vec<_>>();
+        let r = numbers::find::<usize>(s)
+            .values()
+            .collect::<Result<Vec<_>, _>>()?;
 
-        if r.len() != 3 { bail!("no enough nums"); }
-        Ok(Movement { count: r[0], from: r[1], to: r[2] })
+        match r[..] {
+            [count, from, to] if from > 0 && to > 0 => {
+                Ok(Movement { count, from, to })
+            }
+            [_, _, _] => anyhow::bail!("stacks are numbered from 1"),
+            _ => anyhow::bail!("not enough nums"),
+        }
     }
 }
 
-fn main() -> anyhow::Result<()> {
-    // read lines from stdin
-    let lines = std::io::stdin()
-        .lines()
-        .flat_map(|l| l.ok())
-        .collect::<Vec<_>>();
-
-    // the input has two sections:
-    // 1. initial state
-    // 2. movement command
-    // -- they are separated by an empty line
-    let mut init_state: Vec<String> = Vec::new();
-    let mut movements: Vec<String> = Vec::new();
-    let mut has_parsed_init = false;
-    for l in lines {
-        if l.is_empty() {
-            has_parsed_init = true;
-            continue;
-        }
+/// Parse the drawing of the initial state of the stacks.
+fn parse_platform(drawing: &str) -> anyhow::Result<Platform> {
+    // lines may stop short of stacks that are shorter than the rest, so pad
+    // them out to read the drawing column by column
+    let width = drawing.lines().map(str::len).max().context("no stacks")?;
+    let drawing = Grid::from_rows(
+        drawing
+            .lines()
+            .map(|line| format!("{line:width$}").chars().collect())
+            .collect(),
+    )?;
+
+    // the last line numbers the stacks, and each stack is drawn from the top
+    // down in the column of its number
+    drawing
+        .columns()
+        .filter_map(|column| {
+            let mut column = column.rev();
+            column.next().filter(|c| c.is_ascii_digit())?;
+
+            let stack = column
+                .filter(|&&c| c != ' ')
+                .map(|&c| {
+                    if c.is_ascii_uppercase() {
+                        Ok(c)
+                    } else {
+                        Err(anyhow::anyhow!("invalid crate `{c}`"))
+                    }
+                })
+                .collect();
+            Some(stack)
+        })
+        .collect()
+}
+
+/// What crate ends up on top of each stack.
+fn top_crates(platform: &Platform) -> anyhow::Result<String> {
+    platform
+        .iter()
+        .enumerate()
+        .map(|(i, stack)| {
+            stack
+                .last()
+                .with_context(|| format!("stack {} is empty", i + 1))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+struct Procedure {
+    platform: Platform,
+    movements: Vec<Movement>,
+}
+
+impl Procedure {
+    /// Execute every movement on a copy of the platform, giving the crates on
+    /// top of each stack afterwards.
+    fn run(
+        &self,
+        execute: fn(&Movement, &mut Platform) -> anyhow::Result<()>,
+    ) -> anyhow::Result<String> {
+        let mut platform = self.platform.clone();
 
-        match has_parsed_init {
-            false => init_state.push(l.clone()),
-            true => movements.push(l.clone()),
+        // loop through all movements, executing them
+        for (i, m) in self.movements.iter().enumerate() {
+            execute(m, &mut platform).with_context(|| {
+                format!("failed to execute movement {}", i + 1)
+            })?;
         }
+
+        top_crates(&platform)
     }
+}
 
-    // init state needs to be read column by column
-    let chars = init_state.iter()
-        .map(|l| l.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-   
-    let num_stacks = (chars[0].len()+1) / 4;
-    let mut platform = Vec::with_capacity(num_stacks);
-    // loop over the number of stacks, creating a stack and filling it
-    for i in 1..=num_stacks {
-        let ind = column_to_index(i);
-        let mut stack = Vec::new();
-        for line in &chars {
-            stack.push(line[ind]);
-        }
-        // stack currently includes spaces and the column number
-        // -- this needs to be removed before adding to the platform
-        // stack is also in the wrong order
-        let stack = stack.iter()
-            .filter(|&&c| c != ' ' && !c.is_numeric())
-            .map(|c| c.to_owned())
-            .rev()
-            .collect::<Vec<_>>();
-        platform.push(stack);
+struct Day5;
+
+impl Solution for Day5 {
+    type Parsed = Procedure;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        // the input has two sections:
+        // 1. initial state
+        // 2. movement command
+        // -- they are separated by an empty line
+        let Some((init_state, movements)) = input.split_once("\n\n") else {
+            anyhow::bail!("couldn't split the stacks from the movements");
+        };
+
+        Ok(Procedure {
+            platform: parse_platform(init_state)
+                .context("invalid drawing of the stacks")?,
+            movements: aoc_common::parse_lines(movements)?,
+        })
     }
-    
-    // movements...
-    let movements = movements.iter()
-        .filter_map(|s| s.parse::<Movement>().ok())
-        .collect::<VecDeque<_>>();
-
-    // loop through all movements, executing them
-    for m in movements {
-        //m.execute(&mut platform);
-        m.execute_alt(&mut platform);
+
+    fn part1(procedure: &Self::Parsed) -> anyhow::Result<String> {
+        procedure.run(Movement::execute)
     }
-    
-    // what crate ends up on top of each stack
-    let mut result = String::new();
-    for mut stack in platform {
-        result = format!("{}{}", result, stack.pop().unwrap());
+
+    fn part2(procedure: &Self::Parsed) -> anyhow::Result<String> {
+        procedure.run(Movement::execute_alt)
     }
-    println!("Result: {}", result);
 
-    Ok(())
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        const STACKS: usize = 9;
+
+        let crates = ('A'..='Z').collect::<Vec<_>>();
+        let mut heights = (0..STACKS)
+            .map(|_| rng.range(2..10 + size as u64 / 50) as usize)
+            .collect::<Vec<_>>();
+        let mut input = String::new();
+
+        // the drawing goes from the highest crate down to the stack numbers
+        for level in (0..*heights.iter().max()?).rev() {
+            let line = heights
+                .iter()
+                .map(|&height| {
+                    if height > level {
+                        format!("[{}]", rng.pick(&crates))
+                    } else {
+                        String::from("   ")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            input.push_str(line.trim_end());
+            input.push('\n');
+        }
+
+        let numbers = (1..=STACKS)
+            .map(|i| format!(" {i} "))
+            .collect::<Vec<_>>()
+            .join(" ");
+        input.push_str(numbers.trim_end());
+        input.push_str("\n\n");
+
+        // stacks keep at least one crate so that each has a crate on top,
+        // and one always has more to move as there are twice as many crates
+        // as stacks
+        for _ in 0..size {
+            let from = loop {
+                let from = rng.index(STACKS);
+                if heights[from] > 1 {
+                    break from;
+                }
+            };
+            let to = (from + 1 + rng.index(STACKS - 1)) % STACKS;
+            let count = rng.range(1..heights[from].min(30) as u64) as usize;
+
+            heights[from] -= count;
+            heights[to] += count;
+            input.push_str(&format!(
+                "move {count} from {} to {}\n",
+                from + 1,
+                to + 1
+            ));
+        }
+
+        Some(input)
+    }
+}
+
+aoc_common::setup! {
+    "day5",
+    Day5,
+    Example: r"
+    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+    ",
+    Part1: "CMZ",
+    Part2: "MCD",
 }
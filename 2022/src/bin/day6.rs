@@ -4,68 +4,80 @@
 // Day 6
 //
 // https://adventofcode.com/2022/day/6
-#![allow(unused)]
 
-fn any_chars_equal(window: Vec<char>) -> bool {
+use anyhow::Context;
+use aoc_common::{generate::Rng, Solution};
+
+fn any_chars_equal(window: &[char]) -> bool {
     for (ai, a) in window.iter().enumerate() {
-        for (bi, b) in window.iter().enumerate() {
-            // dont compare the same index of the window
-            if ai == bi { continue; }
-            if a == b { return true; }
+        // only compare against the later chars of the window
+        if window[ai + 1..].contains(a) {
+            return true;
         }
     }
 
     false
 }
 
-fn part1(chars: Vec<char>) -> usize {
-    // to process we start at index 4 since this is the first time that
-    // we are able to have a marker (marker is 4 unqiue chars in a row)
+/// Find the number of chars processed before the end of the first marker,
+/// which is `size` unique chars in a row.
+fn find_marker(chars: &[char], size: usize) -> anyhow::Result<usize> {
+    chars
+        .windows(size)
+        .position(|window| !any_chars_equal(window))
+        .map(|i| i + size)
+        .with_context(|| format!("no marker of {size} unique chars"))
+}
 
-    for (i, &c) in chars.iter().enumerate().skip(3) {
-        let window = vec![chars[i-3], chars[i-2], chars[i-1], c];
+struct Day6;
 
-        // are all chars unique?
-        // no? continue looping
-        if any_chars_equal(window) {
-            continue;
-        }
+impl Solution for Day6 {
+    type Parsed = Vec<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-        // otherwise
-        return i+1;
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(input.trim().chars().collect())
     }
 
-    usize::MAX
-}
+    fn part1(chars: &Self::Parsed) -> anyhow::Result<usize> {
+        // a start-of-packet marker is 4 unique chars in a row
+        find_marker(chars, 4)
+    }
 
-fn part2(chars: Vec<char>) -> usize {
-    // now the window must be of size 14
-    for (i, &c) in chars.iter().enumerate().skip(13) {
-        let window = &chars[(i-13)..=i];
+    fn part2(chars: &Self::Parsed) -> anyhow::Result<usize> {
+        // now the window must be of size 14
+        find_marker(chars, 14)
+    }
 
-        // are all chars unique?
-        // no? continue looping
-        if any_chars_equal(window.to_vec()) {
-            continue;
-        }
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        // ten letters can't make a start-of-message marker, so the only one
+        // is at the very end and part 2 has to search the whole stream
+        let letters = ('a'..='j').collect::<Vec<_>>();
+        let mut stream =
+            (0..size).map(|_| *rng.pick(&letters)).collect::<String>();
+        stream.extend('k'..='z');
 
-        // otherwise
-        return i+1;
+        Some(stream)
     }
-
-    usize::MAX
 }
 
-fn main() {
-    let chars = std::io::stdin()
-        .lines()
-        .filter_map(|l| l.ok())
-        .flat_map(|l| l.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let ret = part1(chars.clone());
-    println!("Part 1: {}", ret);
-
-    let ret = part2(chars.clone());
-    println!("Part 2: {}", ret);
+aoc_common::setup! {
+    "day6",
+    Day6,
+    Part1: [
+        example: "mjqjpqmgbljsphdztnvjfqwrcgsmlb" => 7,
+        example2: "bvwbjplbgvbhsrlpgdmjqwftvncz" => 5,
+        example3: "nppdvjthqldpwncqszvftbrmjlhg" => 6,
+        example4: "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg" => 10,
+        example5: "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw" => 11,
+        no_marker: "abcabc" => aoc_common::Fails("no marker"),
+    ],
+    Part2: [
+        example: "mjqjpqmgbljsphdztnvjfqwrcgsmlb" => 19,
+        example2: "bvwbjplbgvbhsrlpgdmjqwftvncz" => 23,
+        example3: "nppdvjthqldpwncqszvftbrmjlhg" => 23,
+        example4: "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg" => 29,
+        example5: "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw" => 26,
+    ],
 }
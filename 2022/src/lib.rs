@@ -0,0 +1,6 @@
+//! Advent of Code 2022.
+
+// the day modules carry their own example tests, which are already run as
+// part of each binary
+#[cfg(not(test))]
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
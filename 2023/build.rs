@@ -0,0 +1,3 @@
+fn main() {
+    aoc_common::build::register_days(2023);
+}
@@ -3,6 +3,9 @@
 
 use std::str::FromStr;
 
+use anyhow::Context;
+use aoc_common::{generate::Rng, Solution};
+
 struct CalibrationValue(u32);
 
 impl From<(char, char)> for CalibrationValue {
@@ -41,102 +44,173 @@ impl FromStr for CalibrationValue {
 
         match (first, last) {
             (Some(f), Some(l)) => Ok((f, l).into()),
-            _ => {
-                dbg!(s);
-                Err(anyhow::anyhow!("`first` and `last` are not set. Are there any numbers in the string?"))
-            }
+            _ => Err(anyhow::anyhow!(
+                "`first` and `last` are not set. Are there any numbers in the \
+                 string?"
+            )),
         }
     }
 }
 
-fn part1(input: &str) -> u32 {
-    // parse each line into a calibration value then sum it
-    input
-        .lines()
-        .map(|line| line.parse::<CalibrationValue>().unwrap().0)
-        .sum()
+/// Parse a line into its calibration value.
+fn calibrate(line: &str) -> anyhow::Result<u32> {
+    let value = line
+        .parse::<CalibrationValue>()
+        .with_context(|| format!("no calibration value in `{line}`"))?;
+
+    Ok(value.0)
 }
 
-fn part2(input: &str) -> u32 {
-    // parse each line into a calibration value then sum it
-    input
-        .lines()
-        // preprocess the line to replace spelled-out number (e.g. "one") with
-        // the corresponding digit
-        // NOTE: this is done left to right, **NOT** in numerical order
-        .map(|line| {
-            let chars: Vec<char> = line.chars().collect();
-            let mut new_chars: Vec<char> = Vec::with_capacity(chars.len());
-
-            let mut i = 0;
-            while i < chars.len() {
-                // match over different combinations of spelled out numbers
-                match (
-                    chars.get(i),
-                    chars.get(i + 1),
-                    chars.get(i + 2),
-                    chars.get(i + 3),
-                    chars.get(i + 4),
-                ) {
-                    (Some('o'), Some('n'), Some('e'), _, _) => {
-                        new_chars.push('1');
-                        i += 3;
-                    }
-                    (Some('t'), Some('w'), Some('o'), _, _) => {
-                        new_chars.push('2');
-                        i += 3;
-                    }
-                    (Some('t'), Some('h'), Some('r'), Some('e'), Some('e')) => {
-                        new_chars.push('3');
-                        i += 5;
-                    }
-                    (Some('f'), Some('o'), Some('u'), Some('r'), _) => {
-                        new_chars.push('4');
-                        i += 4;
-                    }
-                    (Some('f'), Some('i'), Some('v'), Some('e'), _) => {
-                        new_chars.push('5');
-                        i += 5;
-                    }
-                    (Some('s'), Some('i'), Some('x'), _, _) => {
-                        new_chars.push('6');
-                        i += 3;
-                    }
-                    (Some('s'), Some('e'), Some('v'), Some('e'), Some('n')) => {
-                        new_chars.push('7');
-                        i += 5;
-                    }
-                    (Some('e'), Some('i'), Some('g'), Some('h'), Some('t')) => {
-                        new_chars.push('8');
-                        i += 5;
-                    }
-                    (Some('n'), Some('i'), Some('n'), Some('e'), _) => {
-                        new_chars.push('9');
-                        i += 4;
-                    }
-                    (Some(&c), _, _, _, _) => {
-                        new_chars.push(c);
-                        i += 1;
+struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        // each part finds the calibration values differently, so only split
+        // the input into lines here
+        Ok(input.lines().map(str::to_owned).collect())
+    }
+
+    fn part1(lines: &Self::Parsed) -> anyhow::Result<u32> {
+        // parse each line into a calibration value then sum it
+        lines.iter().map(|line| calibrate(line)).sum()
+    }
+
+    fn part2(lines: &Self::Parsed) -> anyhow::Result<u32> {
+        // parse each line into a calibration value then sum it
+        lines
+            .iter()
+            // preprocess the line to replace spelled-out number (e.g. "one")
+            // with the corresponding digit
+            // NOTE: this is done left to right, **NOT** in numerical order
+            .map(|line| {
+                let chars: Vec<char> = line.chars().collect();
+                let mut new_chars: Vec<char> = Vec::with_capacity(chars.len());
+
+                let mut i = 0;
+                while i < chars.len() {
+                    // match over different combinations of spelled out numbers
+                    match (
+                        chars.get(i),
+                        chars.get(i + 1),
+                        chars.get(i + 2),
+                        chars.get(i + 3),
+                        chars.get(i + 4),
+                    ) {
+                        (Some('o'), Some('n'), Some('e'), _, _) => {
+                            new_chars.push('1');
+                            i += 3;
+                        }
+                        (Some('t'), Some('w'), Some('o'), _, _) => {
+                            new_chars.push('2');
+                            i += 3;
+                        }
+                        (
+                            Some('t'),
+                            Some('h'),
+                            Some('r'),
+                            Some('e'),
+                            Some('e'),
+                        ) => {
+                            new_chars.push('3');
+                            i += 5;
+                        }
+                        (Some('f'), Some('o'), Some('u'), Some('r'), _) => {
+                            new_chars.push('4');
+                            i += 4;
+                        }
+                        (Some('f'), Some('i'), Some('v'), Some('e'), _) => {
+                            new_chars.push('5');
+                            i += 5;
+                        }
+                        (Some('s'), Some('i'), Some('x'), _, _) => {
+                            new_chars.push('6');
+                            i += 3;
+                        }
+                        (
+                            Some('s'),
+                            Some('e'),
+                            Some('v'),
+                            Some('e'),
+                            Some('n'),
+                        ) => {
+                            new_chars.push('7');
+                            i += 5;
+                        }
+                        (
+                            Some('e'),
+                            Some('i'),
+                            Some('g'),
+                            Some('h'),
+                            Some('t'),
+                        ) => {
+                            new_chars.push('8');
+                            i += 5;
+                        }
+                        (Some('n'), Some('i'), Some('n'), Some('e'), _) => {
+                            new_chars.push('9');
+                            i += 4;
+                        }
+                        (Some(&c), _, _, _, _) => {
+                            new_chars.push(c);
+                            i += 1;
+                        }
+                        _ => unreachable!("index out of bounds?"),
                     }
-                    _ => unreachable!("index out of bounds?"),
                 }
+
+                new_chars.iter().collect::<String>()
+            })
+            .map(|line| calibrate(&line))
+            .sum()
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        const WORDS: [&str; 9] = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight",
+            "nine",
+        ];
+
+        let mut input = String::new();
+        for _ in 0..size {
+            // every line needs a digit for part 1, among the words and noise
+            let mut pieces = vec![rng.range(1..10).to_string()];
+            for _ in 0..rng.range(2..8) {
+                pieces.push(match rng.range(0..3) {
+                    0 => rng.range(1..10).to_string(),
+                    1 => rng.pick(&WORDS).to_string(),
+                    _ => (0..rng.range(1..5))
+                        .map(|_| (b'a' + rng.range(0..26) as u8) as char)
+                        .collect(),
+                });
             }
+            rng.shuffle(&mut pieces);
 
-            new_chars.iter().collect::<String>()
-        })
-        .map(|line| line.parse::<CalibrationValue>().unwrap().0)
-        .sum()
+            input.push_str(&pieces.concat());
+            input.push('\n');
+        }
+
+        Some(input)
+    }
 }
 
-advent_of_code::setup! {
+aoc_common::setup! {
     "day1",
-    Part1: r"
+    Day1,
+    Part1: [
+        example: r"
 1abc2
 pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet
-    " = 142,
-    Part2: r"
+        " => 142,
+        no_digits: "abcdef" => aoc_common::Fails("no calibration value"),
+    ],
+    Part2: [
+        example: r"
 two1nine
 eightwothree
 abcone2threexyz
@@ -144,5 +218,7 @@ xtwone3four
 4nineeightseven2
 zoneight234
 7pqrstsixteen
-    " = 281,
+        " => 281,
+        eightwothree: "eightwothree" => 83,
+    ],
 }
@@ -3,6 +3,9 @@
 
 use std::str::FromStr;
 
+use anyhow::Context;
+use aoc_common::{generate::Rng, Solution};
+
 struct Game {
     id: u32,
     sets: Vec<CubeSet>,
@@ -73,7 +76,8 @@ impl Game {
             }
         }
 
-        max_red.unwrap() * max_green.unwrap() * max_blue.unwrap()
+        // a colour that is never shown needs no cubes of that colour
+        max_red.unwrap_or(0) * max_green.unwrap_or(0) * max_blue.unwrap_or(0)
     }
 }
 
@@ -86,13 +90,21 @@ impl FromStr for Game {
         };
 
         // get the id from the "Game <number>" part of the `game` string
-        let id = game.split_at(4).1.trim().parse::<u32>().unwrap();
+        let id = game
+            .strip_prefix("Game")
+            .context("game does not start with `Game`")?
+            .trim()
+            .parse::<u32>()
+            .with_context(|| format!("invalid game id in `{game}`"))?;
 
         // split with ';' as delimiter and parse into a cube set
         let sets = sets
             .split(';')
-            .map(|s| s.parse::<CubeSet>().unwrap())
-            .collect::<Vec<_>>();
+            .map(|s| {
+                s.parse::<CubeSet>()
+                    .with_context(|| format!("invalid cube set `{}`", s.trim()))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
 
         Ok(Game { id, sets })
     }
@@ -108,40 +120,78 @@ impl FromStr for CubeSet {
             blue: None,
         };
 
-        s.split(',').for_each(|s| {
-            let (count, colour) = s.trim().split_once(' ').unwrap();
-            let count = count.parse::<u32>().unwrap();
+        for s in s.split(',') {
+            let Some((count, colour)) = s.trim().split_once(' ') else {
+                anyhow::bail!(
+                    "expected `<count> <colour>`, found `{}`",
+                    s.trim()
+                );
+            };
+            let count = count
+                .parse::<u32>()
+                .with_context(|| format!("invalid count `{count}`"))?;
             match colour {
                 "red" => set.red = Some(count),
                 "green" => set.green = Some(count),
                 "blue" => set.blue = Some(count),
-                _ => {}
+                _ => anyhow::bail!("unknown colour `{colour}`"),
             }
-        });
+        }
 
         Ok(set)
     }
 }
 
-fn part1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| line.parse::<Game>().unwrap())
-        .filter(|g| g.is_possible())
-        .map(|g| g.id)
-        .sum()
-}
+struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Vec<Game>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        aoc_common::parse_lines(input)
+    }
+
+    fn part1(games: &Self::Parsed) -> anyhow::Result<u32> {
+        Ok(games.iter().filter(|g| g.is_possible()).map(|g| g.id).sum())
+    }
 
-fn part2(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| line.parse::<Game>().unwrap())
-        .map(|g| g.power())
-        .sum()
+    fn part2(games: &Self::Parsed) -> anyhow::Result<u32> {
+        Ok(games.iter().map(|g| g.power()).sum())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+
+        for id in 1..=size {
+            let mut sets = Vec::new();
+            for _ in 0..rng.range(1..7) {
+                // a set shows at least one colour, in any order
+                let mut cubes = Vec::new();
+                while cubes.is_empty() {
+                    for colour in ["red", "green", "blue"] {
+                        if rng.chance(2, 3) {
+                            cubes
+                                .push(format!("{} {colour}", rng.range(1..21)));
+                        }
+                    }
+                }
+                rng.shuffle(&mut cubes);
+
+                sets.push(cubes.join(", "));
+            }
+
+            input.push_str(&format!("Game {id}: {}\n", sets.join("; ")));
+        }
+
+        Some(input)
+    }
 }
 
-advent_of_code::setup! {
+aoc_common::setup! {
     "day2",
+    Day2,
     Example: r"
 Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
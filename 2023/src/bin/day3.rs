@@ -1,135 +1,69 @@
 // Advent of Code 2023
 // Liam Fenneman
 
+use aoc_common::{
+    generate::Rng,
+    grid::Grid,
+    numbers,
+    point::{Point, Vec2},
+    Solution,
+};
+
 #[derive(Debug, Clone)]
 struct Number {
-    starts_at: usize,
-    ends_at: usize,
-    line_no: usize,
+    start: Point,
+    end: Point,
     value: u32,
 }
 
 impl Number {
-    fn new(digit: char, pos: (usize, usize)) -> Self {
-        Number {
-            starts_at: pos.1,
-            ends_at: pos.1,
-            line_no: pos.0,
-            value: digit.to_string().parse().unwrap(),
-        }
+    /// is there a symbol anywhere around this number
+    fn is_part_number(&self, schematic: &Grid<char>) -> bool {
+        let corner = Vec2::new(1, 1);
+        let around = schematic.region(self.start - corner, self.end + corner);
+
+        around
+            .map(|(_, &ch)| ch)
+            .any(|ch| !ch.is_ascii_digit() && ch != '.')
     }
 
-    fn push_digit(&mut self, digit: char, pos: usize) {
-        assert!(digit.is_ascii_digit());
-
-        self.ends_at = pos;
-
-        let mut current = self.value.to_string();
-        current.push(digit);
-        self.value = current.parse().unwrap();
-    }
-
-    #[allow(clippy::ptr_arg)]
-    fn is_part_number(&self, char_field: &Vec<Vec<char>>) -> bool {
-        // check left
-        if self.starts_at > 0 {
-            if let Some(&x) = char_field[self.line_no].get(self.starts_at - 1) {
-                if !x.is_ascii_digit() && x != '.' {
-                    return true;
-                }
-            }
-        }
-
-        // check right
-        if let Some(&x) = char_field[self.line_no].get(self.ends_at + 1) {
-            if !x.is_ascii_digit() && x != '.' {
-                return true;
-            }
-        }
-
-        // check above
-        if self.line_no > 0 && self.check(&char_field[self.line_no - 1]) {
-            return true;
-        }
-
-        // check below
-        if self.line_no + 1 < char_field.len()
-            && self.check(&char_field[self.line_no + 1])
-        {
-            return true;
-        }
-
-        // otherwise false
-        false
-    }
-
-    fn check(&self, line: &[char]) -> bool {
-        let srt = self.starts_at.saturating_sub(1);
-        let end = usize::min(self.ends_at.saturating_add(1), line.len() - 1);
-        line[srt..=end]
-            .iter()
-            .any(|&ch| !ch.is_ascii_digit() && ch != '.')
-    }
-
-    /// is the given index within the bounds of this number
-    fn check_collision(&self, line_no: usize, index: usize) -> bool {
-        self.line_no == line_no
-            && self.starts_at <= index
-            && self.ends_at >= index
-    }
-
-    fn is_adjcacent_to(&self, line_no: usize, index: usize) -> bool {
-        self.check_collision(line_no - 1, index - 1)
-            || self.check_collision(line_no - 1, index)
-            || self.check_collision(line_no - 1, index + 1)
-            || self.check_collision(line_no, index - 1)
-            || self.check_collision(line_no, index + 1)
-            || self.check_collision(line_no + 1, index - 1)
-            || self.check_collision(line_no + 1, index)
-            || self.check_collision(line_no + 1, index + 1)
+    /// is the given point next to any digit of this number
+    fn is_adjacent_to(&self, point: Point) -> bool {
+        (self.start.x..=self.end.x)
+            .any(|x| Point::new(x, self.start.y).chebyshev(point) == 1)
     }
 }
 
-fn find_numbers(input: &[&str]) -> Vec<Number> {
-    let mut numbers: Vec<Number> = Vec::new();
-    for (l, _) in input.iter().enumerate() {
-        let mut cur_num: Option<Number> = None;
-        let chars = input[l].chars().collect::<Vec<_>>();
-        for (c, &ch) in chars.iter().enumerate() {
-            match ch {
-                _ if ch.is_ascii_digit() => match cur_num {
-                    None => cur_num = Some(Number::new(ch, (l, c))),
-                    Some(ref mut n) => n.push_digit(ch, c),
-                },
-                _ => {
-                    if let Some(ref n) = cur_num {
-                        numbers.push(n.clone());
-                        cur_num = None;
-                    }
-                }
-            }
-        }
-        if let Some(ref n) = cur_num {
-            numbers.push(n.clone());
-            cur_num = None;
-        }
-        assert!(cur_num.is_none());
-    }
-
-    numbers
+/// every number in the schematic, which is ASCII so that the byte offsets
+/// of the numbers are also their columns
+fn find_numbers(input: &str) -> anyhow::Result<Vec<Number>> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(l, line)| {
+            let pos = move |c: usize| Point::new(c as i64, l as i64);
+            numbers::find::<u32>(line).map(move |number| {
+                let number = number?;
+                Ok(Number {
+                    start: pos(number.span.start),
+                    end: pos(number.span.end - 1),
+                    value: number.value,
+                })
+            })
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone)]
 struct Gear {
-    line_no: usize,
-    index: usize,
+    at: Point,
 }
 
 impl Gear {
     fn get_adjacent(&self, numbers: &[Number]) -> Vec<Number> {
         numbers
             .iter()
-            .filter(|n| n.is_adjcacent_to(self.line_no, self.index))
+            .filter(|n| n.is_adjacent_to(self.at))
             .cloned()
             .collect()
     }
@@ -145,46 +79,123 @@ impl Gear {
     }
 }
 
-fn part1(input: &str) -> u32 {
-    let input = input.lines().collect::<Vec<_>>();
+#[derive(Debug, Clone)]
+struct Schematic {
+    char_field: Grid<char>,
+    numbers: Vec<Number>,
+    gears: Vec<Gear>,
+}
+
+struct Day3;
 
-    let numbers = find_numbers(&input);
+impl Solution for Day3 {
+    type Parsed = Schematic;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    let char_field = input
-        .iter()
-        .map(|l| l.chars().collect::<Vec<_>>())
-        .collect();
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        let char_field = input.parse::<Grid<char>>()?;
 
-    numbers
-        .iter()
-        .filter(|n| n.is_part_number(&char_field))
-        .map(|n| n.value)
-        .sum()
-}
+        // find all (potential) gears
+        let gears = char_field
+            .iter()
+            .filter(|&(_, &ch)| ch == '*')
+            .map(|(at, _)| Gear { at })
+            .collect();
 
-fn part2(input: &str) -> u32 {
-    let input = input.lines().collect::<Vec<_>>();
-
-    // find all (potential) gears
-    let mut gears: Vec<Gear> = Vec::new();
-    for (l, s) in input.iter().enumerate() {
-        let chars = s.chars().collect::<Vec<_>>();
-        for (i, &ch) in chars.iter().enumerate() {
-            if ch == '*' {
-                gears.push(Gear {
-                    line_no: l,
-                    index: i,
-                })
+        let numbers = find_numbers(input)?;
+
+        Ok(Schematic {
+            char_field,
+            numbers,
+            gears,
+        })
+    }
+
+    fn part1(schematic: &Self::Parsed) -> anyhow::Result<u32> {
+        Ok(schematic
+            .numbers
+            .iter()
+            .filter(|n| n.is_part_number(&schematic.char_field))
+            .map(|n| n.value)
+            .sum())
+    }
+
+    fn part2(schematic: &Self::Parsed) -> anyhow::Result<u32> {
+        Ok(schematic
+            .gears
+            .iter()
+            .map(|g| g.get_ratio(&schematic.numbers))
+            .sum())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        // the real schematic is 140 wide, so the size is how many lines
+        const WIDTH: usize = 140;
+        const SYMBOLS: &[u8] = b"***#+$/=%@";
+
+        let mut field = (0..size.max(1))
+            .map(|_| {
+                let mut line = Vec::new();
+                while line.len() < WIDTH {
+                    match rng.range(0..10) {
+                        0..=2 => {
+                            line.extend(rng.range(1..1000).to_string().bytes());
+                            line.push(if rng.chance(1, 4) {
+                                *rng.pick(SYMBOLS)
+                            } else {
+                                b'.'
+                            });
+                        }
+                        3 => line.push(*rng.pick(SYMBOLS)),
+                        _ => line.push(b'.'),
+                    }
+                }
+                line.truncate(WIDTH);
+                line
+            })
+            .collect::<Vec<_>>();
+
+        // a gear's ratio is the product of every number next to it, so keep
+        // gears to two numbers like the real input to stop it overflowing
+        for row in 0..field.len() {
+            for col in 0..WIDTH {
+                if field[row][col] != b'*' {
+                    continue;
+                }
+
+                let cols = col.saturating_sub(1)..(col + 2).min(WIDTH);
+                let numbers = field
+                    [row.saturating_sub(1)..(row + 2).min(field.len())]
+                    .iter()
+                    .map(|line| {
+                        // numbers start in the columns, or run into them
+                        cols.clone()
+                            .filter(|&c| line[c].is_ascii_digit())
+                            .filter(|&c| {
+                                c == cols.start || !line[c - 1].is_ascii_digit()
+                            })
+                            .count()
+                    })
+                    .sum::<usize>();
+                if numbers > 2 {
+                    field[row][col] = b'#';
+                }
             }
         }
-    }
 
-    let numbers = find_numbers(&input);
-    gears.iter().map(|g| g.get_ratio(&numbers)).sum()
+        Some(
+            field
+                .into_iter()
+                .map(|line| String::from_utf8_lossy(&line).into_owned() + "\n")
+                .collect(),
+        )
+    }
 }
 
-advent_of_code::setup! {
+aoc_common::setup! {
     "day3",
+    Day3,
     Example: r"
 467..114..
 ...*......
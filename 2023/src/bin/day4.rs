@@ -3,6 +3,9 @@
 
 use std::str::FromStr;
 
+use anyhow::Context;
+use aoc_common::{generate::Rng, Solution};
+
 #[derive(Debug, Clone)]
 struct Card {
     instances: u32,
@@ -28,7 +31,7 @@ impl Card {
         self.winning_set
             .0
             .iter()
-            .filter(|&&n| self.our_set.0.iter().any(|&o| n == o))
+            .filter(|&n| self.our_set.0.contains(n))
             .count() as u32
     }
 
@@ -50,8 +53,8 @@ impl FromStr for Card {
 
         Ok(Card {
             instances: 1,
-            winning_set: winning.parse()?,
-            our_set: ours.parse()?,
+            winning_set: winning.parse().context("invalid winning numbers")?,
+            our_set: ours.parse().context("invalid numbers we have")?,
         })
     }
 }
@@ -63,52 +66,109 @@ impl FromStr for CardSet {
         Ok(CardSet(
             s.split(' ')
                 .filter(|s| !s.is_empty())
-                .map(|s| s.parse().unwrap())
-                .collect(),
+                .map(|s| {
+                    s.parse().with_context(|| format!("invalid number `{s}`"))
+                })
+                .collect::<anyhow::Result<_>>()?,
         ))
     }
 }
 
-fn part1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| line.parse::<Card>().unwrap())
-        .map(|c| c.calc_points())
-        .sum()
-}
+struct Day4;
 
-fn part2(input: &str) -> u32 {
-    let mut cards: Vec<Card> = input
-        .lines()
-        .map(|line| line.parse::<Card>().unwrap())
-        .collect();
+impl Solution for Day4 {
+    type Parsed = Vec<Card>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    let mut total_cards = 0;
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        aoc_common::parse_lines(input)
+    }
+
+    fn part1(cards: &Self::Parsed) -> anyhow::Result<u32> {
+        Ok(cards.iter().map(|c| c.calc_points()).sum())
+    }
 
-    for i in 0..cards.len() {
-        let card = cards[i].clone();
-        let count = cards[i].count_matches();
-        for next in (i + 1)..=(i + count as usize) {
-            if let Some(next_card) = cards.get_mut(next) {
-                next_card.increment_instances(card.instances);
+    fn part2(cards: &Self::Parsed) -> anyhow::Result<u32> {
+        // instances are counted on a copy so the parsed cards stay untouched
+        let mut cards = cards.clone();
+
+        let mut total_cards = 0;
+
+        for i in 0..cards.len() {
+            let card = cards[i].clone();
+            let count = cards[i].count_matches();
+            for next in (i + 1)..=(i + count as usize) {
+                if let Some(next_card) = cards.get_mut(next) {
+                    next_card.increment_instances(card.instances);
+                }
             }
+            total_cards += card.instances;
         }
-        total_cards += card.instances;
+
+        Ok(total_cards)
     }
 
-    total_cards
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut numbers = (1..100).collect::<Vec<u32>>();
+        let list = |numbers: &[u32]| {
+            numbers
+                .iter()
+                .map(|n| format!("{n:>2}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let mut input = String::new();
+
+        for id in 1..=size {
+            // cards only win copies of cards in the same block of ten, so
+            // the number of copies stays bounded however many cards there are
+            let rest = 9 - (id - 1) % 10;
+            let matches = rng.range(0..rest.min(4) as u64 + 1) as usize;
+
+            rng.shuffle(&mut numbers);
+            let (winning, others) = numbers.split_at(10);
+            let mut ours = winning[..matches]
+                .iter()
+                .chain(&others[..25 - matches])
+                .copied()
+                .collect::<Vec<_>>();
+            rng.shuffle(&mut ours);
+
+            input.push_str(&format!(
+                "Card {id:>3}: {} | {}\n",
+                list(winning),
+                list(&ours)
+            ));
+        }
+
+        Some(input)
+    }
 }
 
-advent_of_code::setup! {
+aoc_common::setup! {
     "day4",
-    Example: r"
+    Day4,
+    Part1: [
+        example: r"
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+        " => 13,
+        invalid_number: "Card 1: 1 2 x 3 | 4 5"
+            => aoc_common::Fails("invalid number `x`"),
+    ],
+    Part2: [
+        example: r"
 Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
-    ",
-    Part1: 13,
-    Part2: 30,
+        " => 30,
+    ],
 }
@@ -1,8 +1,19 @@
 // Advent of Code 2023
 // Liam Fenneman
 
-use std::str::FromStr;
-
+use std::{ops::RangeInclusive, str::FromStr};
+
+use anyhow::Context;
+use aoc_common::{
+    generate::Rng,
+    interval::{Interval, IntervalSet},
+    parser::{
+        self, key_value, lines, list, literal, optional, sections, take_while1,
+        unsigned, Parser,
+    },
+    solution::Implementation,
+    Solution,
+};
 use rayon::prelude::*;
 
 #[derive(Debug, Clone)]
@@ -16,9 +27,10 @@ struct Map(Vec<PartialRange>);
 
 #[derive(Debug, Clone)]
 struct PartialRange {
-    dest: u64,
-    src: u64,
-    len: u64,
+    src: Interval,
+    /// how far the seeds in `src` move, which is checked when parsing to
+    /// keep every mapped seed a `u64`
+    offset: i64,
 }
 
 impl Almanac {
@@ -27,7 +39,7 @@ impl Almanac {
         let mut seed = seed;
 
         for map in &self.maps {
-            if let Some(range) = map.0.iter().find(|r| r.is_within(seed)) {
+            if let Some(range) = map.0.iter().find(|r| r.src.contains(seed)) {
                 seed = range.map(seed);
             }
         }
@@ -36,15 +48,46 @@ impl Almanac {
     }
 }
 
+impl Map {
+    /// Map a set of seeds, splitting it where it crosses the edge of a
+    /// partial range. Like [`Almanac::eval`], the first partial range that
+    /// holds a seed is the one that maps it.
+    fn map_seeds(&self, mut unmapped: IntervalSet) -> IntervalSet {
+        let mut mapped = IntervalSet::new();
+
+        for partial in &self.0 {
+            let src = IntervalSet::from(partial.src);
+            mapped.extend(
+                unmapped
+                    .intersection(&src)
+                    .iter()
+                    .filter_map(|seeds| seeds.translate(partial.offset)),
+            );
+
+            // the seeds either side of the partial range are left for the
+            // partial ranges after it
+            unmapped = unmapped.difference(&src);
+        }
+
+        // seeds outside every partial range map to themselves
+        mapped.union(&unmapped)
+    }
+}
+
 impl PartialRange {
-    #[inline]
-    fn is_within(&self, seed: u64) -> bool {
-        seed >= self.src && seed < (self.src + self.len)
+    fn new(dest: u64, src: u64, len: u64) -> Option<Self> {
+        let src = Interval::from_len(src, len)?;
+        let offset = i64::try_from(dest)
+            .ok()?
+            .checked_sub(i64::try_from(src.start()).ok()?)?;
+        src.translate(offset)?;
+
+        Some(PartialRange { src, offset })
     }
 
     #[inline]
     fn map(&self, seed: u64) -> u64 {
-        seed - self.src + self.dest
+        seed.wrapping_add_signed(self.offset)
     }
 }
 
@@ -52,86 +95,147 @@ impl FromStr for Almanac {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let sections: Vec<_> = s.split("\n\n").collect();
-
-        let seeds = sections[0]
-            .split(' ')
-            .skip(1)
-            .map(|s| s.parse().unwrap())
-            .collect();
-
-        let maps = sections[1..]
-            .iter()
-            .map(|s| s.parse::<Map>().unwrap())
-            .collect();
-
-        Ok(Almanac { seeds, maps })
+        let seeds = key_value(literal("seeds"), list(unsigned()));
+
+        let range = unsigned()
+            .then_ignore(literal(" "))
+            .then(unsigned())
+            .then_ignore(literal(" "))
+            .then(unsigned())
+            .try_map(
+                "a partial range whose seeds fit in a `u64`",
+                |((dest, src), len)| PartialRange::new(dest, src, len),
+            );
+
+        // a map can be empty when every seed maps to itself
+        let name = take_while1("a map name", |c| c != ':' && c != '\n');
+        let ranges = optional(literal("\n").ignore_then(lines(range)));
+        let map = key_value(name, ranges)
+            .map(|(_, ranges)| Map(ranges.unwrap_or_default()));
+
+        let almanac = seeds
+            .then_ignore(literal("\n\n"))
+            .then(sections(map))
+            .map(|((_, seeds), maps)| Almanac { seeds, maps });
+
+        Ok(parser::parse_all(almanac, s)?)
     }
 }
 
-impl FromStr for Map {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Map(s
-            .lines()
-            .skip(1)
-            .map(|line| line.parse().unwrap())
-            .collect()))
+/// The seeds read as pairs of the start and length of a range.
+fn seed_ranges(almanac: &Almanac) -> anyhow::Result<Vec<Interval>> {
+    let (pairs, rest) = almanac.seeds.as_chunks::<2>();
+    if !rest.is_empty() {
+        anyhow::bail!("seeds must come in pairs of start and length");
     }
-}
-
-impl FromStr for PartialRange {
-    type Err = anyhow::Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let Some((dest, rest)) = s.split_once(' ') else {
-            anyhow::bail!("partial range dest parse failed");
-        };
-        let Some((src, len)) = rest.split_once(' ') else {
-            anyhow::bail!("partial range dest parse failed");
-        };
-        Ok(PartialRange {
-            dest: dest.parse()?,
-            src: src.parse()?,
-            len: len.parse()?,
+    // a range with a length of 0 has no seeds in it
+    pairs
+        .iter()
+        .filter(|&&[_, len]| len > 0)
+        .map(|&[start, len]| {
+            Interval::from_len(start, len).with_context(|| {
+                format!("seed range `{start} {len}` is too long")
+            })
         })
-    }
+        .collect()
 }
 
-fn part1(input: &str) -> u64 {
-    let almanac: Almanac = input.parse().unwrap();
+struct Day5;
 
-    almanac
-        .seeds
-        .iter()
-        .map(|&seed| almanac.eval(seed))
-        .min()
-        .unwrap()
-}
+impl Solution for Day5 {
+    type Parsed = Almanac;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-fn part2(input: &str) -> u64 {
-    let almanac: Almanac = input.parse().unwrap();
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(almanac: &Self::Parsed) -> anyhow::Result<u64> {
+        almanac
+            .seeds
+            .iter()
+            .map(|&seed| almanac.eval(seed))
+            .min()
+            .context("there are no seeds")
+    }
 
-    assert!(almanac.seeds.len() % 2 == 0);
+    fn part2(almanac: &Self::Parsed) -> anyhow::Result<u64> {
+        let seeds = seed_ranges(almanac)?.into_iter().collect();
 
-    let mut pairs = Vec::new();
-    let mut i = 0;
-    while i < almanac.seeds.len() {
-        pairs.push((almanac.seeds[i], almanac.seeds[i + 1]));
-        i += 2;
+        almanac
+            .maps
+            .iter()
+            .fold(seeds, |seeds, map| map.map_seeds(seeds))
+            .min()
+            .context("there are no seeds")
     }
 
-    pairs
-        .par_iter()
-        .flat_map(|&(start, len)| start..(start + len))
-        .map(|seed| almanac.eval(seed))
-        .min()
-        .unwrap()
+    fn part2_alternatives() -> Vec<Implementation<Almanac, u64>> {
+        vec![("brute_force", |almanac| {
+            seed_ranges(almanac)?
+                .into_par_iter()
+                .flat_map(RangeInclusive::from)
+                .map(|seed| almanac.eval(seed))
+                .min()
+                .context("there are no seeds")
+        })]
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        const CATEGORIES: [&str; 8] = [
+            "seed",
+            "soil",
+            "fertilizer",
+            "water",
+            "light",
+            "temperature",
+            "humidity",
+            "location",
+        ];
+        const LIMIT: u64 = 1 << 32;
+
+        // the seed ranges are kept short for the brute force alternative that
+        // `implementations_agree` compares part 2 against, which tries every
+        // seed
+        let seeds = (0..size.max(1))
+            .map(|_| {
+                let start = rng.range(0..LIMIT - 100);
+                format!("{start} {}", rng.range(1..100))
+            })
+            .collect::<Vec<_>>();
+        let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+        for names in CATEGORIES.windows(2) {
+            input.push_str(&format!("\n{}-to-{} map:\n", names[0], names[1]));
+
+            // the sources split the numbers into ranges that don't overlap,
+            // some of which are left out so they map to themselves
+            let mut cuts =
+                (0..size).map(|_| rng.range(1..LIMIT)).collect::<Vec<_>>();
+            cuts.extend([0, LIMIT]);
+            cuts.sort();
+            cuts.dedup();
+
+            for src in cuts.windows(2) {
+                let (src, len) = (src[0], src[1] - src[0]);
+                if rng.chance(1, 4) {
+                    continue;
+                }
+
+                let dest = rng.range(0..LIMIT - len + 1);
+                input.push_str(&format!("{dest} {src} {len}\n"));
+            }
+        }
+
+        Some(input)
+    }
 }
 
-advent_of_code::setup! {
+aoc_common::setup! {
     "day5",
+    Day5,
     Example: r"
 seeds: 79 14 55 13
 
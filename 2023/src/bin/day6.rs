@@ -4,6 +4,7 @@
 use std::str::FromStr;
 
 use anyhow::Context;
+use aoc_common::{generate::Rng, solution::Implementation, Solution};
 
 #[derive(Debug)]
 struct Race {
@@ -19,7 +20,10 @@ impl FromStr for Races {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (times, distances) = s.trim().split_once('\n').context("")?;
+        let (times, distances) = s
+            .trim()
+            .split_once('\n')
+            .context("expected a line of times and a line of distances")?;
         let times = times.split_ascii_whitespace();
         let distances = distances.split_ascii_whitespace();
 
@@ -27,16 +31,23 @@ impl FromStr for Races {
             .zip(distances)
             .skip(1)
             .map(|(t, d)| {
-                (t.parse::<u64>().unwrap(), d.parse::<u64>().unwrap())
+                Ok(Race {
+                    time: t
+                        .parse()
+                        .with_context(|| format!("invalid time `{t}`"))?,
+                    distance: d
+                        .parse()
+                        .with_context(|| format!("invalid distance `{d}`"))?,
+                })
             })
-            .map(|(time, distance)| Race { time, distance })
-            .collect::<Vec<_>>();
+            .collect::<anyhow::Result<Vec<_>>>()?;
 
         Ok(Races(races))
     }
 }
 
 impl Race {
+    /// Count the wins by trying every time the button could be held for.
     fn count_wins(&self) -> u64 {
         let mut count = 0;
 
@@ -50,33 +61,124 @@ impl Race {
 
         count
     }
+
+    fn beats_record(&self, hold: u64) -> bool {
+        ACCEL * hold * (self.time - hold) > self.distance
+    }
+
+    /// Count the wins from the roots of `ACCEL * hold * (time - hold) =
+    /// distance`, between which every hold time beats the record.
+    fn count_wins_quadratic(&self) -> u64 {
+        let (accel, time, distance) = (
+            u128::from(ACCEL),
+            u128::from(self.time),
+            u128::from(self.distance),
+        );
+        let Some(discriminant) =
+            (accel * time * time).checked_sub(4 * distance)
+        else {
+            return 0;
+        };
+
+        // the integer square root can be off by one either way from the
+        // real root, so step onto the first hold that beats the record
+        let root =
+            (accel * time - (accel * discriminant).isqrt()) / (2 * accel);
+        let mut hold =
+            u64::try_from(root).unwrap_or(u64::MAX).min(self.time / 2);
+        while hold > 0 && self.beats_record(hold - 1) {
+            hold -= 1;
+        }
+        while hold <= self.time / 2 && !self.beats_record(hold) {
+            hold += 1;
+        }
+
+        // the wins are symmetric about half the time
+        if hold > self.time / 2 {
+            return 0;
+        }
+        self.time - 2 * hold + 1
+    }
 }
 
-fn part1(input: &str) -> u64 {
-    let races: Races = input.parse().unwrap();
-    races.0.iter().map(|r| r.count_wins()).product()
+impl Races {
+    /// The single race found by ignoring the spaces between the numbers.
+    fn combined(&self) -> anyhow::Result<Race> {
+        let join = |value: fn(&Race) -> u64| {
+            self.0
+                .iter()
+                .map(|r| value(r).to_string())
+                .collect::<String>()
+                .parse::<u64>()
+                .context("combined race does not fit in a u64")
+        };
+
+        Ok(Race {
+            time: join(|r| r.time)?,
+            distance: join(|r| r.distance)?,
+        })
+    }
 }
 
-fn part2(input: &str) -> u64 {
-    let (times, distances) = input.trim().split_once('\n').context("").unwrap();
-    let time = times
-        .split_ascii_whitespace()
-        .skip(1)
-        .collect::<String>()
-        .parse::<u64>()
-        .unwrap();
-    let distance = distances
-        .split_ascii_whitespace()
-        .skip(1)
-        .collect::<String>()
-        .parse::<u64>()
-        .unwrap();
-
-    Race { time, distance }.count_wins()
+struct Day6;
+
+impl Solution for Day6 {
+    type Parsed = Races;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(races: &Self::Parsed) -> anyhow::Result<u64> {
+        Ok(races.0.iter().map(|r| r.count_wins_quadratic()).product())
+    }
+
+    fn part2(races: &Self::Parsed) -> anyhow::Result<u64> {
+        Ok(races.combined()?.count_wins_quadratic())
+    }
+
+    fn part1_alternatives() -> Vec<Implementation<Races, u64>> {
+        vec![("brute_force", |races| {
+            Ok(races.0.iter().map(|r| r.count_wins()).product())
+        })]
+    }
+
+    fn part2_alternatives() -> Vec<Implementation<Races, u64>> {
+        vec![("brute_force", |races| Ok(races.combined()?.count_wins()))]
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        // the size is roughly the time of the combined race, which is split
+        // into up to four races of similar length that can't start with a 0
+        let digits = size.clamp(1, 999_999_999).to_string().len();
+        let races = digits.min(4);
+
+        let (mut times, mut distances) = (Vec::new(), Vec::new());
+        for race in 0..races {
+            let len = (digits + race) / races;
+            // a race of 1ms can't go any distance, so it can't be won
+            let shortest = 10u64.pow(len as u32 - 1).max(2);
+            let time = rng.range(shortest..10u64.pow(len as u32));
+
+            // the record is beaten by some of the ways to play the race
+            let best = (time / 2) * (time - time / 2);
+            times.push(time.to_string());
+            distances.push(rng.range(best / 2..best).to_string());
+        }
+
+        Some(format!(
+            "Time: {}\nDistance: {}\n",
+            times.join(" "),
+            distances.join(" ")
+        ))
+    }
 }
 
-advent_of_code::setup! {
+aoc_common::setup! {
     "day6",
+    Day6,
     Example: r"
 Time:      7  15   30
 Distance:  9  40  200
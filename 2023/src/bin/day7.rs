@@ -3,6 +3,9 @@
 
 use std::{collections::BTreeMap, str::FromStr};
 
+use anyhow::Context;
+use aoc_common::{generate::Rng, Solution};
+
 #[derive(Debug, Clone)]
 struct Hand<C> {
     cards: Vec<C>,
@@ -205,18 +208,24 @@ impl<C> FromStr for Hand<C>
 where
     C: TryFrom<char>,
     C: std::fmt::Debug,
-    <C as std::convert::TryFrom<char>>::Error: std::fmt::Debug,
+    <C as std::convert::TryFrom<char>>::Error: Into<anyhow::Error>,
 {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let Some((cards, bid)) = s.split_once(' ') else {
-            anyhow::bail!("failed to parse");
+            anyhow::bail!("expected `<cards> <bid>`");
         };
 
         Ok(Hand {
-            bid: bid.trim().parse()?,
-            cards: cards.chars().map(|c| c.try_into().unwrap()).collect(),
+            bid: bid
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid bid `{}`", bid.trim()))?,
+            cards: cards
+                .chars()
+                .map(|c| c.try_into().map_err(Into::into))
+                .collect::<anyhow::Result<_>>()?,
         })
     }
 }
@@ -239,7 +248,7 @@ impl TryFrom<char> for CardPart1 {
             '4' => Self::_4,
             '3' => Self::_3,
             '2' => Self::_2,
-            _ => anyhow::bail!("failed to parse card"),
+            _ => anyhow::bail!("invalid card `{value}`"),
         })
     }
 }
@@ -262,16 +271,24 @@ impl TryFrom<char> for CardPart2 {
             '4' => Self::_4,
             '3' => Self::_3,
             '2' => Self::_2,
-            _ => anyhow::bail!("failed to parse card"),
+            _ => anyhow::bail!("invalid card `{value}`"),
         })
     }
 }
 
-fn part1(input: &str) -> u64 {
-    let mut hands = input
-        .lines()
-        .map(|line| line.parse::<Hand<CardPart1>>().unwrap())
-        .collect::<Vec<_>>();
+/// The hands read with the card rules of each part.
+#[derive(Debug, Clone)]
+struct Hands {
+    part1: Vec<Hand<CardPart1>>,
+    part2: Vec<Hand<CardPart2>>,
+}
+
+fn total_winnings<C>(hands: &[Hand<C>]) -> u64
+where
+    C: Clone,
+    Hand<C>: Ord,
+{
+    let mut hands = hands.to_vec();
 
     hands.sort();
     hands.reverse();
@@ -283,25 +300,46 @@ fn part1(input: &str) -> u64 {
         .sum()
 }
 
-fn part2(input: &str) -> u64 {
-    let mut hands = input
-        .lines()
-        .inspect(|line| eprintln!("{:?}", line))
-        .map(|line| line.parse::<Hand<CardPart2>>().unwrap())
-        .collect::<Vec<_>>();
+struct Day7;
 
-    hands.sort();
-    hands.reverse();
+impl Solution for Day7 {
+    type Parsed = Hands;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    hands
-        .iter()
-        .enumerate()
-        .map(|(idx, hand)| (idx as u64 + 1) * hand.bid)
-        .sum()
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(Hands {
+            part1: aoc_common::parse_lines(input)?,
+            part2: aoc_common::parse_lines(input)?,
+        })
+    }
+
+    fn part1(hands: &Self::Parsed) -> anyhow::Result<u64> {
+        Ok(total_winnings(&hands.part1))
+    }
+
+    fn part2(hands: &Self::Parsed) -> anyhow::Result<u64> {
+        Ok(total_winnings(&hands.part2))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let cards = "23456789TJQKA".chars().collect::<Vec<_>>();
+
+        Some(
+            (0..size)
+                .map(|_| {
+                    let hand =
+                        (0..5).map(|_| *rng.pick(&cards)).collect::<String>();
+                    format!("{hand} {}\n", rng.range(1..1001))
+                })
+                .collect(),
+        )
+    }
 }
 
-advent_of_code::setup! {
+aoc_common::setup! {
     "day7",
+    Day7,
     Example: r"
 32T3K 765
 T55J5 684
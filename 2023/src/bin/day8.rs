@@ -3,6 +3,13 @@
 
 use std::{collections::HashMap, str::FromStr};
 
+use anyhow::Context;
+use aoc_common::{
+    generate::Rng,
+    parser::{self, lines, literal, take_while1, tuple, Parser},
+    Solution,
+};
+
 #[derive(Debug, Clone)]
 struct Node {
     label: String,
@@ -28,47 +35,43 @@ impl FromStr for NodeMap {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let label = take_while1("a label", |c| c.is_ascii_alphanumeric());
+        let node = (&label)
+            .then_ignore(literal(" = "))
+            .then(tuple(&label, &label))
+            .map(|(label, (left, right))| Node {
+                label: label.to_owned(),
+                left: left.to_owned(),
+                right: right.to_owned(),
+            });
+
         Ok(NodeMap(
-            s.lines()
-                .map(|line| line.parse::<Node>().unwrap())
-                .map(|n| (n.label.clone(), n.clone()))
+            parser::parse_all(lines(node), s)?
+                .into_iter()
+                .map(|n| (n.label.clone(), n))
                 .collect(),
         ))
     }
 }
 
-impl FromStr for Node {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (label, tuple) = s.trim().split_once(" = ").unwrap();
-
-        let (left, right) =
-            tuple[1..tuple.len() - 1].trim().split_once(", ").unwrap();
-
-        Ok(Node {
-            label: label.to_owned(),
-            left: left.to_owned(),
-            right: right.to_owned(),
-        })
-    }
-}
-
 impl FromStr for Instructions {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Instructions {
-            inner: s
-                .chars()
-                .map(|ch| match ch {
-                    'L' => Instruction::Left,
-                    'R' => Instruction::Right,
-                    _ => panic!("invalid input"),
-                })
-                .collect(),
-            index: 0,
-        })
+        let inner = s
+            .chars()
+            .map(|ch| match ch {
+                'L' => Ok(Instruction::Left),
+                'R' => Ok(Instruction::Right),
+                _ => anyhow::bail!("invalid instruction `{ch}`"),
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        if inner.is_empty() {
+            anyhow::bail!("there are no instructions");
+        }
+
+        Ok(Instructions { inner, index: 0 })
     }
 }
 
@@ -98,80 +101,196 @@ fn lcm(a: u64, b: u64) -> u64 {
     a * b / gcd(a, b)
 }
 
-fn part1(input: &str) -> u64 {
-    let (instructions, nodes) = input.trim().split_once("\n\n").unwrap();
-
-    let nodes: NodeMap = nodes.parse().unwrap();
+struct Network {
+    instructions: Instructions,
+    nodes: NodeMap,
+}
 
-    let instructions = instructions.parse::<Instructions>().unwrap();
+impl Network {
+    /// Most steps that can be taken before a node and instruction pair must
+    /// repeat, after which the walk is going round in circles.
+    fn max_steps(&self) -> u64 {
+        (self.nodes.0.len() * self.instructions.inner.len()) as u64
+    }
+}
 
-    let mut count = 0;
-    let mut current_node = nodes.0.get("AAA");
+impl NodeMap {
+    fn get(&self, label: &str) -> anyhow::Result<&Node> {
+        self.0
+            .get(label)
+            .with_context(|| format!("node `{label}` does not exist"))
+    }
+}
 
-    for ins in instructions {
-        if current_node.is_some_and(|n| n.label == "ZZZ")
-            || current_node.is_none()
-        {
-            break;
-        }
+struct Day8;
 
-        let cn = current_node.unwrap();
+impl Solution for Day8 {
+    type Parsed = Network;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-        match ins {
-            Instruction::Left => current_node = nodes.0.get(&cn.left),
-            Instruction::Right => current_node = nodes.0.get(&cn.right),
-        }
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        let Some((instructions, nodes)) = input.trim().split_once("\n\n")
+        else {
+            anyhow::bail!("couldn't split instructions from nodes");
+        };
 
-        count += 1;
+        Ok(Network {
+            instructions: instructions
+                .parse()
+                .context("invalid instructions")?,
+            nodes: nodes.parse().context("invalid nodes")?,
+        })
     }
 
-    count
-}
+    fn part1(network: &Self::Parsed) -> anyhow::Result<u64> {
+        let nodes = &network.nodes;
+        let limit = network.max_steps();
 
-fn part2(input: &str) -> u64 {
-    let (instructions, nodes) = input.trim().split_once("\n\n").unwrap();
-    let map: NodeMap = nodes.parse().unwrap();
-
-    let start_nodes = map
-        .0
-        .keys()
-        .filter(|k| k.ends_with('A'))
-        .cloned()
-        .collect::<Vec<String>>();
-    let mut counts = Vec::new();
-
-    for node in start_nodes.iter() {
-        let mut current = node.clone();
-        let mut idx = 0;
-        let mut current_ins = instructions.chars().nth(idx).unwrap();
         let mut count = 0;
-        while !current.ends_with('Z') {
-            current = match current_ins {
-                'R' => map.0.get(&current).unwrap().right.clone(),
-                'L' => map.0.get(&current).unwrap().left.clone(),
-                _ => panic!("Invalid direction"),
-            };
-            idx = (idx + 1) % instructions.len();
-            current_ins = instructions.chars().nth(idx).unwrap();
+        let mut current_node = nodes.get("AAA")?;
+
+        for ins in network.instructions.clone() {
+            if current_node.label == "ZZZ" {
+                break;
+            }
+
+            if count > limit {
+                anyhow::bail!("`ZZZ` can't be reached from `AAA`");
+            }
+
+            match ins {
+                Instruction::Left => {
+                    current_node = nodes.get(&current_node.left)?
+                }
+                Instruction::Right => {
+                    current_node = nodes.get(&current_node.right)?
+                }
+            }
+
             count += 1;
         }
-        counts.push(count);
+
+        Ok(count)
     }
-    let count = counts.iter().fold(1, |acc, x| lcm(acc, *x as u64));
 
-    count
+    fn part2(network: &Self::Parsed) -> anyhow::Result<u64> {
+        let map = &network.nodes;
+
+        let start_nodes = map
+            .0
+            .keys()
+            .filter(|k| k.ends_with('A'))
+            .cloned()
+            .collect::<Vec<String>>();
+        let mut counts = Vec::new();
+
+        for node in start_nodes.iter() {
+            let mut current = node.clone();
+            let mut instructions = network.instructions.clone();
+            let mut count = 0;
+            while !current.ends_with('Z') {
+                if count > network.max_steps() {
+                    anyhow::bail!("no `Z` node can be reached from `{node}`");
+                }
+
+                let node = map.get(&current)?;
+                current = match instructions.next() {
+                    Some(Instruction::Right) => node.right.clone(),
+                    Some(Instruction::Left) => node.left.clone(),
+                    None => unreachable!("instructions repeat forever"),
+                };
+                count += 1;
+            }
+            counts.push(count);
+        }
+        let count = counts.iter().fold(1, |acc, &x| lcm(acc, x));
+
+        Ok(count)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        // every ghost walks its own loop from an `A` node to a `Z` node, with
+        // a prime number of times the same length so the steps for them all
+        // to finish still fit in a u64
+        const PRIMES: [u64; 6] = [2, 3, 5, 7, 11, 13];
+        let base = (size as u64 / PRIMES.iter().sum::<u64>()).max(1);
+
+        // other labels only use `B` to `Y` so they never end in `A` or `Z`
+        let mut next = 0;
+        let mut label = || {
+            next += 1;
+            let mut n = next;
+            let mut label = Vec::new();
+            while n > 0 || label.len() < 3 {
+                label.push(b'B' + (n % 24) as u8);
+                n /= 24;
+            }
+            String::from_utf8_lossy(&label).into_owned()
+        };
+
+        let mut nodes = Vec::new();
+        for (ghost, prime) in PRIMES.into_iter().enumerate() {
+            let (start, end) = match ghost {
+                0 => (String::from("AAA"), String::from("ZZZ")),
+                _ => {
+                    let name = (b'B' + ghost as u8) as char;
+                    (format!("{name}{name}A"), format!("{name}{name}Z"))
+                }
+            };
+
+            let mut walk = vec![start];
+            walk.extend((1..base * prime).map(|_| label()));
+            walk.push(end);
+
+            // the end leads back round the loop, past the start
+            for (i, node) in walk.iter().enumerate() {
+                let next = walk.get(i + 1).unwrap_or(&walk[1]);
+                nodes.push(format!("{node} = ({next}, {next})"));
+            }
+        }
+        rng.shuffle(&mut nodes);
+
+        let instructions = (0..rng.range(8..300))
+            .map(|_| *rng.pick(&['L', 'R']))
+            .collect::<String>();
+
+        Some(format!("{instructions}\n\n{}\n", nodes.join("\n")))
+    }
 }
 
-advent_of_code::setup! {
+aoc_common::setup! {
     "day8",
-    Part1: r"
+    Day8,
+    Part1: [
+        example: r"
 LLR
 
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)
-    " = 6,
-    Part2: r"
+        " => 6,
+        example_rl: r"
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+        " => 2,
+        unreachable: r"
+LR
+
+AAA = (BBB, BBB)
+BBB = (AAA, AAA)
+ZZZ = (ZZZ, ZZZ)
+        " => aoc_common::Fails("`ZZZ` can't be reached"),
+    ],
+    Part2: [
+        example: r"
 LR
 
 11A = (11B, XXX)
@@ -182,5 +301,6 @@ LR
 22C = (22Z, 22Z)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)
-    " = 6,
+        " => 6,
+    ],
 }
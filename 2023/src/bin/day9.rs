@@ -3,26 +3,52 @@
 
 use std::str::FromStr;
 
+use anyhow::Context;
+use aoc_common::{generate::Rng, Solution};
+
 #[derive(Debug, Clone)]
 struct History(Vec<i64>);
 
 impl History {
-    fn predict_end(&self) -> i64 {
-        let diff_list = find_diff_list(self.0.clone(), vec![]);
+    fn predict_end(&self) -> anyhow::Result<i64> {
+        let last = *self.0.last().context("history is empty")?;
+        let diff_list = self.diff_list()?;
+
+        // a history of all zeros has nothing to extrapolate
+        if diff_list.is_empty() {
+            return Ok(last);
+        }
 
         let index = diff_list.len() - 1;
         let incr = extrapolate_right(diff_list, index, 0);
 
-        self.0.last().unwrap() + incr
+        Ok(last + incr)
     }
 
-    fn predict_start(&self) -> i64 {
-        let diff_list = find_diff_list(self.0.clone(), vec![]);
+    fn predict_start(&self) -> anyhow::Result<i64> {
+        let first = *self.0.first().context("history is empty")?;
+        let diff_list = self.diff_list()?;
+
+        // a history of all zeros has nothing to extrapolate
+        if diff_list.is_empty() {
+            return Ok(first);
+        }
 
         let index = diff_list.len() - 1;
         let incr = extrapolate_left(diff_list, index, 0);
 
-        self.0.first().unwrap() - incr
+        Ok(first - incr)
+    }
+
+    fn diff_list(&self) -> anyhow::Result<Vec<Vec<i64>>> {
+        let diff_list = find_diff_list(self.0.clone(), vec![]);
+
+        // the differences run out of values before they are all zero
+        if diff_list.last().is_some_and(|list| list.is_empty()) {
+            anyhow::bail!("history never reaches all zeros");
+        }
+
+        Ok(diff_list)
     }
 }
 
@@ -74,35 +100,77 @@ impl FromStr for History {
         Ok(History(
             s.split_ascii_whitespace()
                 .filter(|s| !s.is_empty())
-                .map(|s| s.parse::<i64>().unwrap())
-                .collect(),
+                .map(|s| {
+                    s.parse::<i64>()
+                        .with_context(|| format!("invalid value `{s}`"))
+                })
+                .collect::<anyhow::Result<_>>()?,
         ))
     }
 }
 
-fn part1(input: &str) -> i64 {
-    input
-        .lines()
-        .map(|line| line.parse::<History>().unwrap())
-        .map(|h| h.predict_end())
-        .sum()
-}
+struct Day9;
 
-fn part2(input: &str) -> i64 {
-    input
-        .lines()
-        .map(|line| line.parse::<History>().unwrap())
-        .map(|h| h.predict_start())
-        .sum()
+impl Solution for Day9 {
+    type Parsed = Vec<History>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        aoc_common::parse_lines(input)
+    }
+
+    fn part1(histories: &Self::Parsed) -> anyhow::Result<i64> {
+        histories.iter().map(|h| h.predict_end()).sum()
+    }
+
+    fn part2(histories: &Self::Parsed) -> anyhow::Result<i64> {
+        histories.iter().map(|h| h.predict_start()).sum()
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+
+        // each history is a polynomial, so its differences reach all zeros
+        for _ in 0..size {
+            let coefficients = (0..rng.range(1..7))
+                .map(|_| rng.range(0..9) as i64 - 4)
+                .collect::<Vec<_>>();
+            let history = (0..21i64)
+                .map(|x| {
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |value, c| value * x + c)
+                        .to_string()
+                })
+                .collect::<Vec<_>>();
+
+            input.push_str(&history.join(" "));
+            input.push('\n');
+        }
+
+        Some(input)
+    }
 }
 
-advent_of_code::setup! {
+aoc_common::setup! {
     "day9",
-    Example: r"
+    Day9,
+    Part1: [
+        example: r"
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+        " => 114,
+        invalid_value: "0 3 6\n8.3 1 2"
+            => aoc_common::Fails("invalid value `8.3`"),
+    ],
+    Part2: [
+        example: r"
 0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45
-    ",
-    Part1: 114,
-    Part2: 2,
+        " => 2,
+    ],
 }
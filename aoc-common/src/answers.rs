@@ -0,0 +1,207 @@
+//! Known answers for the real puzzle inputs, used to catch regressions.
+//!
+//! The answers live in `answers.toml` inside each year's crate directory (or
+//! the file named by `AOC_ANSWERS`), keyed by year, day and part:
+//!
+//! ```toml
+//! [2023.7]
+//! part1 = 6440
+//! part2 = "5905"
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::{registry::Day, solution::normalise};
+
+/// Environment variable naming the answers file to use instead.
+pub const ANSWERS_VAR: &str = "AOC_ANSWERS";
+
+/// The known answers, keyed by `(year, day, part)`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers(HashMap<(u16, u8, u8), String>);
+
+impl Answers {
+    /// Path of the answers file for the year's crate at `root`.
+    pub fn path(root: &Path) -> PathBuf {
+        std::env::var_os(ANSWERS_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| root.join("answers.toml"))
+    }
+
+    /// Load the answers file for the year's crate at `root`, which is treated
+    /// as empty if it doesn't exist.
+    pub fn load(root: &Path) -> anyhow::Result<Self> {
+        let path = Self::path(root);
+
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+
+        let file = crate::read_file(path.clone())?;
+        file.parse()
+            .with_context(|| format!("invalid answers in `{}`", path.display()))
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(year, day, part)).map(String::as_str)
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table = s.parse::<toml::Table>()?;
+        let mut answers = HashMap::new();
+
+        for (year, days) in &table {
+            let year = year.parse::<u16>().context("invalid year")?;
+            let days = days.as_table().context("year must be a table")?;
+
+            for (day, parts) in days {
+                let day = day.parse::<u8>().context("invalid day")?;
+                let parts = parts.as_table().context("day must be a table")?;
+
+                for (part, answer) in parts {
+                    let part = match part.as_str() {
+                        "part1" => 1,
+                        "part2" => 2,
+                        _ => anyhow::bail!("unknown part `{part}`"),
+                    };
+
+                    // answers compare as text, so numbers can be written bare
+                    let answer = match answer {
+                        toml::Value::String(s) => s.clone(),
+                        toml::Value::Integer(n) => n.to_string(),
+                        _ => anyhow::bail!(
+                            "answer for {year} day {day} part {part} must be \
+                             a string or integer"
+                        ),
+                    };
+
+                    answers.insert((year, day, part), answer);
+                }
+            }
+        }
+
+        Ok(Answers(answers))
+    }
+}
+
+/// How an answer compares to the known answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+}
+
+/// The result of checking one part against the known answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub part: u8,
+    pub answer: String,
+    pub status: Status,
+}
+
+impl Check {
+    fn new(answers: &Answers, day: &Day, part: u8, answer: String) -> Self {
+        let status = match answers.get(day.year, day.day, part) {
+            Some(expected) if normalise(expected) == answer => Status::Correct,
+            Some(expected) => Status::Wrong {
+                expected: expected.to_owned(),
+            },
+            None => Status::Unknown,
+        };
+
+        Check {
+            part,
+            answer,
+            status,
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.status {
+            Status::Correct => write!(f, "ok ({})", self.answer),
+            Status::Wrong { expected } => {
+                write!(f, "WRONG (expected {expected}, got {})", self.answer)
+            }
+            Status::Unknown => write!(f, "no answer ({})", self.answer),
+        }
+    }
+}
+
+/// Solve the day's real input and check both parts against the answers.
+pub fn check(
+    day: &Day,
+    input: &str,
+    answers: &Answers,
+) -> anyhow::Result<[Check; 2]> {
+    let run = (day.solve)(input)?;
+
+    Ok([
+        Check::new(answers, day, 1, run.part1?.value),
+        Check::new(answers, day, 2, run.part2?.value),
+    ])
+}
+
+/// Check the day's real input against the known answers, for the test that
+/// `setup!` generates.
+///
+/// Does nothing unless both the input file and an answer for the day exist.
+pub fn test_real_input(day: &Day) {
+    let answers = Answers::load(day.root.as_ref()).unwrap();
+    if (1..=2).all(|part| answers.get(day.year, day.day, part).is_none()) {
+        return;
+    }
+
+    let Ok(input) = crate::read_file(crate::input_path(day)) else {
+        return;
+    };
+
+    for check in check(day, &input, &answers).unwrap() {
+        if let Status::Wrong { expected } = check.status {
+            assert_eq!(expected, check.answer, "part {}", check.part);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let answers = r#"
+            [2023.7]
+            part1 = 6440
+            part2 = "5905"
+
+            [2022.5]
+            part1 = "CMZ"
+        "#
+        .parse::<Answers>()
+        .unwrap();
+
+        assert_eq!(Some("6440"), answers.get(2023, 7, 1));
+        assert_eq!(Some("5905"), answers.get(2023, 7, 2));
+        assert_eq!(Some("CMZ"), answers.get(2022, 5, 1));
+        assert_eq!(None, answers.get(2022, 5, 2));
+    }
+
+    #[test]
+    fn parse_answers_errors() {
+        assert!("[2023.7]\npart3 = 1".parse::<Answers>().is_err());
+        assert!("[2023.day7]\npart1 = 1".parse::<Answers>().is_err());
+        assert!("[2023.7]\npart1 = 1.5".parse::<Answers>().is_err());
+    }
+}
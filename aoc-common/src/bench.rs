@@ -0,0 +1,209 @@
+//! Benchmarking of a [`Solution`]'s parse and solve phases.
+
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+
+use crate::Solution;
+
+/// How many times to run each phase when benchmarking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Untimed runs before any samples are taken.
+    pub warmup: usize,
+    /// Timed runs of each phase.
+    pub iterations: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            warmup: 3,
+            iterations: 10,
+        }
+    }
+}
+
+impl Options {
+    /// Read `--bench [--iterations <n>] [--warmup <n>]` from the arguments.
+    ///
+    /// Gives `None` unless `--bench` was passed.
+    pub fn from_args(args: &[String]) -> anyhow::Result<Option<Self>> {
+        if !args.iter().any(|a| a == "--bench") {
+            return Ok(None);
+        }
+
+        let default = Options::default();
+        let options = Options {
+            warmup: crate::parse_flag(args, "--warmup")?
+                .unwrap_or(default.warmup),
+            iterations: crate::parse_flag(args, "--iterations")?
+                .unwrap_or(default.iterations),
+        };
+
+        if options.iterations == 0 {
+            anyhow::bail!("`--iterations` must be at least 1");
+        }
+
+        Ok(Some(options))
+    }
+}
+
+/// Summary of the wall time taken by a phase over every iteration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    /// Summarise the samples, which must not be empty.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarise");
+
+        samples.sort();
+
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+
+        Stats {
+            min: samples[0],
+            median,
+            mean: samples.iter().sum::<Duration>() / samples.len() as u32,
+        }
+    }
+}
+
+/// Timings of every phase of a day.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub options: Options,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} warmup, {} iterations",
+            self.options.warmup, self.options.iterations
+        )?;
+        writeln!(f, "{:<8}{:>12}{:>12}{:>12}", "", "min", "median", "mean")?;
+
+        for (name, stats) in [
+            ("parse", self.parse),
+            ("part 1", self.part1),
+            ("part 2", self.part2),
+        ] {
+            writeln!(
+                f,
+                "{:<8}{:>12}{:>12}{:>12}",
+                name,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.mean),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn sample<T>(iterations: usize, mut f: impl FnMut() -> T) -> Stats {
+    let samples = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+
+    Stats::from_samples(samples)
+}
+
+/// Repeatedly parse the input and solve both parts, timing each phase.
+pub fn bench<S: Solution>(
+    input: &str,
+    options: &Options,
+) -> anyhow::Result<Report> {
+    // solve once up front so that a bad input fails before any timing
+    let parsed = S::parse(input).context("failed to parse the input")?;
+    S::part1(&parsed).context("failed to solve part 1")?;
+    S::part2(&parsed).context("failed to solve part 2")?;
+
+    for _ in 0..options.warmup {
+        black_box(S::parse(black_box(input)))?;
+        black_box(S::part1(black_box(&parsed)))?;
+        black_box(S::part2(black_box(&parsed)))?;
+    }
+
+    Ok(Report {
+        options: *options,
+        parse: sample(options.iterations, || S::parse(black_box(input))),
+        part1: sample(options.iterations, || S::part1(black_box(&parsed))),
+        part2: sample(options.iterations, || S::part2(black_box(&parsed))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn stats_odd_samples() {
+        let stats = Stats::from_samples(vec![ms(5), ms(1), ms(3)]);
+        assert_eq!(ms(1), stats.min);
+        assert_eq!(ms(3), stats.median);
+        assert_eq!(ms(3), stats.mean);
+    }
+
+    #[test]
+    fn stats_even_samples() {
+        let stats = Stats::from_samples(vec![ms(4), ms(1), ms(2), ms(9)]);
+        assert_eq!(ms(1), stats.min);
+        assert_eq!(ms(3), stats.median);
+        assert_eq!(ms(4), stats.mean);
+    }
+
+    #[test]
+    fn options_from_args() {
+        let args =
+            |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(None, Options::from_args(&args(&[])).unwrap());
+        assert_eq!(
+            Some(Options::default()),
+            Options::from_args(&args(&["--bench"])).unwrap()
+        );
+        assert_eq!(
+            Some(Options {
+                warmup: 0,
+                iterations: 50
+            }),
+            Options::from_args(&args(&[
+                "--bench",
+                "--iterations",
+                "50",
+                "--warmup=0"
+            ]))
+            .unwrap()
+        );
+        assert!(Options::from_args(&args(&["--bench", "--iterations", "0"]))
+            .is_err());
+    }
+}
@@ -0,0 +1,56 @@
+//! Generates the registry of a year's days, for the year crate's `build.rs`.
+//!
+//! Every `src/bin/dayN.rs` is included as a module of the year's library so
+//! that a single runner can reach each day's `part1`/`part2` through the `DAY`
+//! item that `setup!` generates.
+
+use std::{env, fmt::Write, fs, path::PathBuf};
+
+/// Register every day in the crate's `src/bin` as part of the year.
+///
+/// Writes `days.rs` to `OUT_DIR`, which defines a module for each day and a
+/// `YEAR` [`Year`](crate::Year) collecting them, and tells `setup!` the year.
+pub fn register_days(year: u16) {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let bin_dir = manifest_dir.join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+    println!("cargo:rustc-env=AOC_YEAR={year}");
+
+    let mut days = fs::read_dir(&bin_dir)
+        .expect("could not read src/bin")
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let number = name
+                .strip_prefix("day")?
+                .strip_suffix(".rs")?
+                .parse::<u8>()
+                .ok()?;
+            Some((number, entry.path()))
+        })
+        .collect::<Vec<_>>();
+    days.sort();
+
+    let mut out = String::new();
+    for (number, path) in &days {
+        writeln!(out, "#[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(out, "pub mod day{number};").unwrap();
+    }
+    writeln!(out, "\n/// Every day in this crate, in order.").unwrap();
+    writeln!(
+        out,
+        "pub static YEAR: aoc_common::Year = aoc_common::Year {{"
+    )
+    .unwrap();
+    writeln!(out, "    year: {year},").unwrap();
+    writeln!(out, "    root: env!(\"CARGO_MANIFEST_DIR\"),").unwrap();
+    writeln!(out, "    days: &[").unwrap();
+    for (number, _) in &days {
+        writeln!(out, "        &day{number}::DAY,").unwrap();
+    }
+    writeln!(out, "    ],\n}};").unwrap();
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("days.rs"), out).expect("could not write days.rs");
+}
@@ -0,0 +1,587 @@
+//! A client for the Advent of Code website, to download puzzle inputs and
+//! submit answers.
+//!
+//! The client logs in with the `session` cookie from the website, read from
+//! `AOC_SESSION`. Requests go to `AOC_BASE_URL` if it is set, which lets the
+//! client be pointed at a local server. The website asks automated tools to
+//! say who runs them, so put contact details in `AOC_USER_AGENT`.
+
+use std::{
+    collections::HashSet,
+    fmt, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use anyhow::Context;
+
+/// Environment variable holding the session token.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Environment variable naming the website to use instead.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// The Advent of Code website.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the user agent to identify the client with.
+pub const USER_AGENT_VAR: &str = "AOC_USER_AGENT";
+
+/// The user agent used when `AOC_USER_AGENT` isn't set.
+pub const DEFAULT_USER_AGENT: &str =
+    concat!("aoc-common/", env!("CARGO_PKG_VERSION"));
+
+/// A logged in client for the website.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client::with_user_agent(base_url, session, DEFAULT_USER_AGENT)
+    }
+
+    pub fn with_user_agent(
+        base_url: &str,
+        session: &str,
+        user_agent: &str,
+    ) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.trim().to_owned(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(user_agent)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Create a client from `AOC_SESSION`, `AOC_BASE_URL` and
+    /// `AOC_USER_AGENT`.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let session = std::env::var(SESSION_VAR).with_context(|| {
+            format!("set `{SESSION_VAR}` to the session cookie of the website")
+        })?;
+        let base_url = std::env::var(BASE_URL_VAR)
+            .unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned());
+        let user_agent = std::env::var(USER_AGENT_VAR)
+            .unwrap_or_else(|_| DEFAULT_USER_AGENT.to_owned());
+
+        Ok(Client::with_user_agent(&base_url, &session, &user_agent))
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Fetch the puzzle input for the day.
+    pub fn fetch_input(&self, year: u16, day: u8) -> anyhow::Result<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|e| request_error(e, year, day))?;
+
+        response
+            .into_string()
+            .with_context(|| format!("failed to read the input from `{url}`"))
+    }
+
+    /// Save the puzzle input for the day to `path`, unless it has already
+    /// been saved there.
+    ///
+    /// Gives whether the input had to be downloaded.
+    pub fn download_input(
+        &self,
+        year: u16,
+        day: u8,
+        path: &Path,
+    ) -> anyhow::Result<bool> {
+        // `aoc new` leaves an empty input file to be filled in
+        if fs::metadata(path).is_ok_and(|m| m.len() > 0) {
+            return Ok(false);
+        }
+
+        let input = self.fetch_input(year, day)?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, input).with_context(|| {
+            format!("could not save input to `{}`", path.display())
+        })?;
+
+        Ok(true)
+    }
+
+    /// Submit the answer to a part, unless it is already known to be wrong.
+    ///
+    /// Wrong answers are remembered so they are never submitted again.
+    pub fn submit(
+        &self,
+        wrong: &mut WrongAnswers,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> anyhow::Result<Verdict> {
+        let answer = answer.trim();
+        if answer.is_empty() || answer.contains('\n') {
+            anyhow::bail!("`{answer}` can't be submitted as an answer");
+        }
+        if wrong.contains(year, day, part, answer) {
+            anyhow::bail!(
+                "`{answer}` is already known to be wrong for {year} day {day} \
+                 part {part}"
+            );
+        }
+
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| request_error(e, year, day))?;
+
+        let page = response.into_string().with_context(|| {
+            format!("failed to read the response of `{url}`")
+        })?;
+        let verdict = page.parse::<Verdict>()?;
+
+        if let Verdict::Wrong { .. } = verdict {
+            wrong.insert(year, day, part, answer)?;
+        }
+
+        Ok(verdict)
+    }
+}
+
+/// Explain why a request to the website failed.
+fn request_error(error: ureq::Error, year: u16, day: u8) -> anyhow::Error {
+    match error {
+        ureq::Error::Status(400, _) => anyhow::anyhow!(
+            "the website didn't accept the session token, check `{SESSION_VAR}`"
+        ),
+        ureq::Error::Status(404, _) => {
+            anyhow::anyhow!("{year} day {day} isn't unlocked yet")
+        }
+        e => anyhow::Error::new(e).context("request to the website failed"),
+    }
+}
+
+/// Whether a wrong answer was too high or too low.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// The website's response to an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong {
+        hint: Option<Hint>,
+    },
+    /// An answer was submitted too recently, so this one wasn't checked.
+    TooSoon {
+        wait: Option<Duration>,
+    },
+    /// The part has already been solved.
+    AlreadySolved,
+}
+
+/// The text of the page's `<article>`, without any markup.
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = true; // still inside the opening `<article ...>`
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse a wait such as `1m 20s` from `You have 1m 20s left to wait`.
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    wait.split_whitespace()
+        .map(|part| {
+            let (n, unit) =
+                part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let n = n.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(n * 3600),
+                "m" => Some(n * 60),
+                "s" => Some(n),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+impl FromStr for Verdict {
+    type Err = anyhow::Error;
+
+    fn from_str(page: &str) -> Result<Self, Self::Err> {
+        let text = article_text(page);
+
+        if text.contains("That's the right answer") {
+            Ok(Verdict::Right)
+        } else if text.contains("That's not the right answer") {
+            let hint = if text.contains("your answer is too high") {
+                Some(Hint::TooHigh)
+            } else if text.contains("your answer is too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            Ok(Verdict::Wrong { hint })
+        } else if text.contains("You gave an answer too recently") {
+            Ok(Verdict::TooSoon {
+                wait: parse_wait(&text),
+            })
+        } else if text.contains("Did you already complete it") {
+            Ok(Verdict::AlreadySolved)
+        } else {
+            anyhow::bail!("unrecognised response from the website: `{text}`")
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "right answer"),
+            Verdict::Wrong { hint: None } => write!(f, "wrong answer"),
+            Verdict::Wrong {
+                hint: Some(Hint::TooHigh),
+            } => write!(f, "wrong answer (too high)"),
+            Verdict::Wrong {
+                hint: Some(Hint::TooLow),
+            } => write!(f, "wrong answer (too low)"),
+            Verdict::TooSoon { wait: Some(wait) } => {
+                write!(
+                    f,
+                    "too soon, wait {}s before trying again",
+                    wait.as_secs()
+                )
+            }
+            Verdict::TooSoon { wait: None } => {
+                write!(f, "too soon, wait before trying again")
+            }
+            Verdict::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// Answers the website has said are wrong, kept in a file with a line of
+/// `<year> <day> <part> <answer>` for each.
+#[derive(Debug, Clone, Default)]
+pub struct WrongAnswers {
+    path: PathBuf,
+    answers: HashSet<(u16, u8, u8, String)>,
+}
+
+impl WrongAnswers {
+    /// Path of the wrong answers file for the year's crate at `root`.
+    pub fn path(root: &Path) -> PathBuf {
+        root.join("wrong_answers.txt")
+    }
+
+    /// Load the wrong answers file, which is treated as empty if it doesn't
+    /// exist.
+    pub fn load(path: PathBuf) -> anyhow::Result<Self> {
+        let mut answers = HashSet::new();
+
+        if path.exists() {
+            let file = crate::read_file(path.clone())?;
+
+            for (i, line) in file.lines().enumerate() {
+                let entry = parse_entry(line).with_context(|| {
+                    format!("invalid line {} of `{}`", i + 1, path.display())
+                })?;
+                answers.insert(entry);
+            }
+        }
+
+        Ok(WrongAnswers { path, answers })
+    }
+
+    pub fn contains(&self, year: u16, day: u8, part: u8, answer: &str) -> bool {
+        self.answers
+            .contains(&(year, day, part, answer.trim().to_owned()))
+    }
+
+    /// Remember a wrong answer, adding it to the file.
+    pub fn insert(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> anyhow::Result<()> {
+        let answer = answer.trim().to_owned();
+        if !self.answers.insert((year, day, part, answer.clone())) {
+            return Ok(());
+        }
+
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{year} {day} {part} {answer}"))
+            .with_context(|| {
+                format!(
+                    "could not save wrong answer to `{}`",
+                    self.path.display()
+                )
+            })
+    }
+}
+
+fn parse_entry(line: &str) -> anyhow::Result<(u16, u8, u8, String)> {
+    let mut fields = line.splitn(4, ' ');
+    let mut next = |name| fields.next().with_context(|| format!("no {name}"));
+
+    let year = next("year")?.parse().context("invalid year")?;
+    let day = next("day")?.parse().context("invalid day")?;
+    let part = next("part")?.parse().context("invalid part")?;
+    let answer = next("answer")?.to_owned();
+
+    Ok((year, day, part, answer))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc, thread};
+
+    use super::*;
+
+    const RIGHT: &str = r#"<main>
+<article><p>That's the right answer!  You are <span class="day-success">one gold star</span> closer to restoring snow operations. <a href="/2023/day/7#part2">[Continue to Part Two]</a></p></article>
+</main>"#;
+
+    const TOO_HIGH: &str = r#"<main>
+<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href="/2023/about">about page</a>.  Please wait one minute before trying again. <a href="/2023/day/7">[Return to Day 7]</a></p></article>
+</main>"#;
+
+    const TOO_SOON: &str = r#"<main>
+<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 20s left to wait. <a href="/2023/day/7">[Return to Day 7]</a></p></article>
+</main>"#;
+
+    const SOLVED: &str = r#"<main>
+<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href="/2023/day/7">[Return to Day 7]</a></p></article>
+</main>"#;
+
+    /// A request received by the stub server.
+    #[derive(Debug)]
+    struct Request {
+        method: String,
+        url: String,
+        cookie: Option<String>,
+        user_agent: Option<String>,
+        body: String,
+    }
+
+    /// Start a local server that gives each response in turn, then gives back
+    /// every request it received once no more arrive.
+    fn stub(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, mpsc::Receiver<Vec<Request>>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let mut requests = Vec::new();
+            let mut responses = responses.into_iter();
+
+            while let Ok(Some(mut request)) =
+                server.recv_timeout(Duration::from_millis(500))
+            {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let header = |name| {
+                    request
+                        .headers()
+                        .iter()
+                        .find(|h| h.field.equiv(name))
+                        .map(|h| h.value.to_string())
+                };
+                requests.push(Request {
+                    method: request.method().to_string(),
+                    url: request.url().to_owned(),
+                    cookie: header("Cookie"),
+                    user_agent: header("User-Agent"),
+                    body,
+                });
+
+                let (status, page) = responses.next().unwrap_or((500, ""));
+                let response = tiny_http::Response::from_string(page)
+                    .with_status_code(status);
+                request.respond(response).unwrap();
+            }
+
+            // some tests don't look at the requests
+            let _ = tx.send(requests);
+        });
+
+        (url, rx)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("aoc-client-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parse_verdicts() {
+        assert_eq!(Verdict::Right, RIGHT.parse().unwrap());
+        assert_eq!(
+            Verdict::Wrong {
+                hint: Some(Hint::TooHigh)
+            },
+            TOO_HIGH.parse().unwrap()
+        );
+        assert_eq!(
+            Verdict::TooSoon {
+                wait: Some(Duration::from_secs(80))
+            },
+            TOO_SOON.parse().unwrap()
+        );
+        assert_eq!(Verdict::AlreadySolved, SOLVED.parse().unwrap());
+        assert!("<article><p>Hello</p></article>"
+            .parse::<Verdict>()
+            .is_err());
+    }
+
+    #[test]
+    fn fetch_input_with_session() {
+        let (url, requests) = stub(vec![(200, "1 2 3\n")]);
+        let client = Client::new(&url, "abc123\n");
+
+        assert_eq!("1 2 3\n", client.fetch_input(2023, 7).unwrap());
+
+        let requests = requests.recv().unwrap();
+        assert_eq!(1, requests.len());
+        assert_eq!("GET", requests[0].method);
+        assert_eq!("/2023/day/7/input", requests[0].url);
+        assert_eq!(Some("session=abc123"), requests[0].cookie.as_deref());
+        assert_eq!(Some(DEFAULT_USER_AGENT), requests[0].user_agent.as_deref());
+    }
+
+    #[test]
+    fn custom_user_agent() {
+        let (url, requests) = stub(vec![(200, "")]);
+        let client = Client::with_user_agent(&url, "abc123", "me@example.com");
+
+        client.fetch_input(2023, 7).unwrap();
+        assert_eq!(
+            Some("me@example.com"),
+            requests.recv().unwrap()[0].user_agent.as_deref()
+        );
+    }
+
+    #[test]
+    fn fetch_input_errors() {
+        let (url, _) = stub(vec![(400, "Please log in"), (404, "Not Found")]);
+        let client = Client::new(&url, "expired");
+
+        let err = client.fetch_input(2023, 7).unwrap_err();
+        assert!(err.to_string().contains(SESSION_VAR));
+
+        let err = client.fetch_input(2023, 25).unwrap_err();
+        assert_eq!("2023 day 25 isn't unlocked yet", err.to_string());
+    }
+
+    #[test]
+    fn download_input_once() {
+        let dir = temp_dir("download");
+        let path = dir.join("input").join("day7.txt");
+        let (url, requests) = stub(vec![(200, "32T3K 765\n")]);
+        let client = Client::new(&url, "abc123");
+
+        assert!(client.download_input(2023, 7, &path).unwrap());
+        assert!(!client.download_input(2023, 7, &path).unwrap());
+        assert_eq!("32T3K 765\n", fs::read_to_string(&path).unwrap());
+        assert_eq!(1, requests.recv().unwrap().len());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn submit_remembers_wrong_answers() {
+        let dir = temp_dir("submit");
+        let path = WrongAnswers::path(&dir);
+        let (url, requests) = stub(vec![(200, TOO_HIGH), (200, RIGHT)]);
+        let client = Client::new(&url, "abc123");
+        let mut wrong = WrongAnswers::load(path.clone()).unwrap();
+
+        let verdict = client.submit(&mut wrong, 2023, 7, 1, "6441").unwrap();
+        assert_eq!(
+            Verdict::Wrong {
+                hint: Some(Hint::TooHigh)
+            },
+            verdict
+        );
+
+        // the wrong answer is never sent again, even after reloading
+        let mut wrong = WrongAnswers::load(path.clone()).unwrap();
+        let err = client.submit(&mut wrong, 2023, 7, 1, "6441").unwrap_err();
+        assert!(err.to_string().contains("already known to be wrong"));
+
+        let verdict = client.submit(&mut wrong, 2023, 7, 1, "6440").unwrap();
+        assert_eq!(Verdict::Right, verdict);
+
+        let requests = requests.recv().unwrap();
+        assert_eq!(2, requests.len());
+        assert_eq!("POST", requests[0].method);
+        assert_eq!("/2023/day/7/answer", requests[0].url);
+        assert_eq!("level=1&answer=6441", requests[0].body);
+        assert_eq!("level=1&answer=6440", requests[1].body);
+        assert_eq!("2023 7 1 6441\n", fs::read_to_string(&path).unwrap());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn wrong_answers_file() {
+        let dir = temp_dir("wrong");
+        let path = WrongAnswers::path(&dir);
+        fs::write(&path, "2022 5 1 CMZ\n2023 7 2 5905\n").unwrap();
+
+        let wrong = WrongAnswers::load(path.clone()).unwrap();
+        assert!(wrong.contains(2022, 5, 1, "CMZ"));
+        assert!(wrong.contains(2023, 7, 2, "5905"));
+        assert!(!wrong.contains(2023, 7, 1, "5905"));
+
+        fs::write(&path, "2023 7\n").unwrap();
+        assert!(WrongAnswers::load(path).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
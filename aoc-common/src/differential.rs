@@ -0,0 +1,325 @@
+//! Differential testing of the implementations of a part against each other.
+//!
+//! A day can keep more than one implementation of a part, such as a brute
+//! force alongside a faster version, by listing them in
+//! [`Solution::part1_alternatives`] and [`Solution::part2_alternatives`].
+//! They are run on the examples and on generated inputs, and any input they
+//! disagree about is shrunk to the smallest one that they still disagree
+//! about.
+
+use std::{fmt, fmt::Display, panic};
+
+use crate::{
+    generate,
+    parallel::panic_message,
+    solution::{self, Implementation},
+    Solution,
+};
+
+/// The sizes of generated inputs that are tried, smallest first, with a few
+/// seeds each.
+const SIZES: [usize; 13] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 20, 50, 100];
+const SEEDS: u64 = 4;
+
+/// The [`normalise`](solution::normalise)d answer of an implementation, or
+/// why it failed.
+type Outcome = Result<String, String>;
+
+/// An input that the implementations of a part don't agree about.
+#[derive(Debug, Clone)]
+pub struct Disagreement {
+    pub part: u8,
+    pub input: String,
+    /// What each implementation gave, in the order they were registered.
+    pub outcomes: Vec<(&'static str, Outcome)>,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "the implementations of part {} disagree:", self.part)?;
+        for (name, outcome) in &self.outcomes {
+            match outcome {
+                Ok(answer) if answer.contains('\n') => {
+                    writeln!(f, "  {name}:\n{answer}")?
+                }
+                Ok(answer) => writeln!(f, "  {name}: {answer}")?,
+                Err(e) => writeln!(f, "  {name}: failed: {e}")?,
+            }
+        }
+
+        write!(f, "on the input:\n{}", self.input)
+    }
+}
+
+/// Run an implementation, catching any panic as a failure.
+fn outcome<P, A: Display>(
+    parsed: &P,
+    part: fn(&P) -> anyhow::Result<A>,
+) -> Outcome {
+    let answer = panic::catch_unwind(panic::AssertUnwindSafe(|| part(parsed)))
+        .map_err(|payload| format!("panicked: {}", panic_message(&*payload)))?;
+
+    answer
+        .map(|answer| solution::normalise(&answer.to_string()))
+        .map_err(|e| format!("{e:#}"))
+}
+
+/// Run every implementation of a part, if there is more than one. They agree
+/// when they give the same answer or when they all fail.
+fn outcomes<P, A: Display>(
+    parsed: &P,
+    main: Implementation<P, A>,
+    alternatives: Vec<Implementation<P, A>>,
+) -> Option<Vec<(&'static str, Outcome)>> {
+    if alternatives.is_empty() {
+        return None;
+    }
+
+    let outcomes = [main]
+        .into_iter()
+        .chain(alternatives)
+        .map(|(name, part)| (name, outcome(parsed, part)))
+        .collect::<Vec<_>>();
+
+    let first = outcomes[0].1.as_ref().ok();
+    if outcomes.iter().all(|(_, o)| o.as_ref().ok() == first) {
+        return None;
+    }
+
+    Some(outcomes)
+}
+
+/// Whether the solution has more than one implementation of either part.
+pub fn has_alternatives<S: Solution>() -> bool {
+    !S::part1_alternatives().is_empty() || !S::part2_alternatives().is_empty()
+}
+
+/// Run every implementation of each part on the input, giving the first
+/// part they disagree about.
+///
+/// Inputs that can't be parsed, or that panic while parsing, give `None`
+/// since no implementation gets to run.
+pub fn compare<S: Solution>(input: &str) -> Option<Disagreement> {
+    let parsed = panic::catch_unwind(|| S::parse(input)).ok()?.ok()?;
+
+    let disagreement = |part, outcomes| Disagreement {
+        part,
+        input: input.to_owned(),
+        outcomes,
+    };
+
+    if let Some(o) =
+        outcomes(&parsed, ("part1", S::part1), S::part1_alternatives())
+    {
+        return Some(disagreement(1, o));
+    }
+
+    outcomes(&parsed, ("part2", S::part2), S::part2_alternatives())
+        .map(|o| disagreement(2, o))
+}
+
+/// Shrink the input by removing pieces of it for as long as the
+/// implementations still disagree about the same part.
+///
+/// Whole lines are removed first, in halves then quarters and so on, and
+/// then single words from what's left of each line.
+pub fn shrink<S: Solution>(mut disagreement: Disagreement) -> Disagreement {
+    let part = disagreement.part;
+    let input = disagreement.input.clone();
+    let mut still_disagrees = |input: &str| {
+        compare::<S>(input)
+            .filter(|d| d.part == part)
+            .map(|d| disagreement = d)
+            .is_some()
+    };
+
+    let mut lines = input.lines().map(str::to_owned).collect::<Vec<_>>();
+    remove_pieces(&mut lines, |lines| still_disagrees(&lines.join("\n")));
+
+    for i in 0..lines.len() {
+        let mut words = lines[i].split(' ').map(str::to_owned).collect();
+        remove_pieces(&mut words, |words| {
+            let mut candidate = lines.clone();
+            candidate[i] = words.join(" ");
+            still_disagrees(&candidate.join("\n"))
+        });
+        lines[i] = words.join(" ");
+    }
+
+    disagreement
+}
+
+/// Remove runs of pieces, from half of them down to one at a time, keeping
+/// each removal that `keep` accepts.
+fn remove_pieces(
+    pieces: &mut Vec<String>,
+    mut keep: impl FnMut(&[String]) -> bool,
+) {
+    let mut run = pieces.len() / 2;
+
+    while run > 0 {
+        let mut start = 0;
+        while start < pieces.len() {
+            let end = (start + run).min(pieces.len());
+            let candidate = [&pieces[..start], &pieces[end..]].concat();
+
+            if !candidate.is_empty() && keep(&candidate) {
+                *pieces = candidate;
+            } else {
+                start = end;
+            }
+        }
+
+        run /= 2;
+    }
+}
+
+/// Check that every implementation of each part agrees on the examples and
+/// on generated inputs of increasing size, for the test that `setup!`
+/// generates.
+///
+/// Panics with the smallest input found that they disagree about. Does
+/// nothing if neither part has alternative implementations.
+pub fn test_implementations<S: Solution>(examples: &[&str]) {
+    if !has_alternatives::<S>() {
+        return;
+    }
+
+    let examples = examples
+        .iter()
+        .map(|example| Some(solution::example(example).to_owned()));
+    let generated = SIZES.into_iter().flat_map(|size| {
+        (0..SEEDS).map(move |seed| generate::generate::<S>(seed, size))
+    });
+
+    let disagreement = examples
+        .chain(generated)
+        .map_while(|input| input)
+        .find_map(|input| compare::<S>(&input));
+
+    if let Some(disagreement) = disagreement {
+        panic!("{}", shrink::<S>(disagreement));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sums the numbers on each line, except that the alternatives get it
+    /// wrong when there are 3s or any 5 at all.
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed = Vec<Vec<u32>>;
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+            input
+                .lines()
+                .map(|line| {
+                    line.split(' ')
+                        .map(|n| Ok(n.parse()?))
+                        .collect::<anyhow::Result<_>>()
+                })
+                .collect()
+        }
+
+        fn part1(lines: &Self::Parsed) -> anyhow::Result<u32> {
+            Ok(lines.iter().flatten().sum())
+        }
+
+        fn part2(lines: &Self::Parsed) -> anyhow::Result<u32> {
+            Ok(lines.iter().flatten().sum())
+        }
+
+        fn part1_alternatives() -> Vec<Implementation<Self::Parsed, u32>> {
+            vec![
+                ("same", Self::part1),
+                ("no_threes", |lines| {
+                    Ok(lines.iter().flatten().filter(|&&n| n != 3).sum())
+                }),
+            ]
+        }
+
+        fn part2_alternatives() -> Vec<Implementation<Self::Parsed, u32>> {
+            vec![("no_fives", |lines| {
+                let numbers = lines.iter().flatten();
+                if numbers.clone().any(|&n| n == 5) {
+                    anyhow::bail!("found a 5");
+                }
+
+                Ok(numbers.sum())
+            })]
+        }
+
+        fn generate(rng: &mut generate::Rng, size: usize) -> Option<String> {
+            let line = |rng: &mut generate::Rng| {
+                (0..4)
+                    .map(|_| rng.range(4..7).to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+
+            Some((0..size).map(|_| line(rng)).collect::<Vec<_>>().join("\n"))
+        }
+    }
+
+    #[test]
+    fn agreeing_inputs() {
+        assert!(compare::<Sum>("1 2\n4").is_none());
+        assert!(compare::<Sum>("not a number").is_none());
+    }
+
+    #[test]
+    fn disagreeing_input() {
+        let disagreement = compare::<Sum>("1 2\n3 4").unwrap();
+        assert_eq!(1, disagreement.part);
+        assert_eq!(
+            vec![
+                ("part1", Ok(String::from("10"))),
+                ("same", Ok(String::from("10"))),
+                ("no_threes", Ok(String::from("7"))),
+            ],
+            disagreement.outcomes
+        );
+
+        let disagreement = compare::<Sum>("1 5").unwrap();
+        assert_eq!(2, disagreement.part);
+        assert_eq!(Err(String::from("found a 5")), disagreement.outcomes[1].1);
+    }
+
+    #[test]
+    fn shrinks_to_smallest_input() {
+        let disagreement = compare::<Sum>("1 2\n4 3 6\n7\n8 9").unwrap();
+        let shrunk = shrink::<Sum>(disagreement);
+
+        assert_eq!(1, shrunk.part);
+        assert_eq!("3", shrunk.input);
+        assert_eq!(
+            "the implementations of part 1 disagree:\n  part1: 3\n  same: 3\n  \
+             no_threes: 0\non the input:\n3",
+            shrunk.to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "part 1 disagree")]
+    fn finds_disagreement_in_examples() {
+        test_implementations::<Sum>(&["\n1 2\n4\n    ", "3"]);
+    }
+
+    #[test]
+    fn finds_disagreement_in_generated_inputs() {
+        // the examples agree, so the 5 has to come from a generated input
+        let panic = panic::catch_unwind(|| {
+            test_implementations::<Sum>(&["1 2"]);
+        })
+        .unwrap_err();
+
+        let message = panic_message(&*panic);
+        assert!(message.contains("part 2 disagree"), "{message}");
+        assert!(message.ends_with("on the input:\n5"), "{message}");
+    }
+}
@@ -0,0 +1,184 @@
+//! Generators of synthetic inputs of any size, for seeing how a day's
+//! solution scales beyond the fixed size of the real input.
+//!
+//! A day opts in by implementing [`Solution::generate`]. Every input is drawn
+//! from an [`Rng`] seeded with a number, so the same seed and size always
+//! give the same input and a run can be repeated.
+
+use std::ops::Range;
+
+use crate::{registry::Day, Solution};
+
+/// The seed used unless `--seed` is given.
+pub const DEFAULT_SEED: u64 = 2023;
+
+/// A small, seeded random number generator (SplitMix64).
+///
+/// Good enough for making up inputs, and written here so that the inputs for
+/// a seed never change with the version of a dependency.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in the range, which must not be empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(!range.is_empty(), "empty range {range:?}");
+
+        // the bias from the modulo is far too small to matter for inputs
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    /// An index into a slice of `len` items, which must not be empty.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..len as u64) as usize
+    }
+
+    /// A random item of the slice, which must not be empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Whether an event with a chance of `n` in `d` happened.
+    pub fn chance(&mut self, n: u64, d: u64) -> bool {
+        self.range(0..d) < n
+    }
+
+    /// Shuffle the items in place.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+/// The size and seed of a generated input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// What the size counts is up to each day, usually lines of input.
+    pub size: usize,
+    pub seed: u64,
+}
+
+impl Options {
+    /// Read `--generated <size> [--seed <n>]` from the arguments.
+    ///
+    /// Gives `None` unless `--generated` was passed.
+    pub fn from_args(args: &[String]) -> anyhow::Result<Option<Self>> {
+        let seed = crate::parse_flag(args, "--seed")?;
+
+        match crate::parse_flag(args, "--generated")? {
+            Some(size) => Ok(Some(Options {
+                size,
+                seed: seed.unwrap_or(DEFAULT_SEED),
+            })),
+            None if seed.is_some() => {
+                anyhow::bail!("`--seed` can only be used with `--generated`")
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Generate an input for the solution, or `None` if it has no generator.
+pub fn generate<S: Solution>(seed: u64, size: usize) -> Option<String> {
+    S::generate(&mut Rng::new(seed), size)
+}
+
+/// Check that the day's generator gives the same input for the same seed,
+/// and that both parts can solve it, for the test that `setup!` generates.
+///
+/// Does nothing if the day has no generator.
+pub fn test_generated(day: &Day) {
+    for (seed, size) in [(DEFAULT_SEED, 1), (DEFAULT_SEED, 50), (7, 200)] {
+        let Some(input) = (day.generate)(seed, size) else {
+            return;
+        };
+        assert_eq!(Some(&input), (day.generate)(seed, size).as_ref());
+
+        let run = (day.solve)(&input)
+            .unwrap_or_else(|e| panic!("size {size}, seed {seed}: {e:#}"));
+        for (part, answer) in [(1, run.part1), (2, run.part2)] {
+            if let Err(e) = answer {
+                panic!("size {size}, seed {seed}, part {part}: {e:#}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(1), numbers(1));
+        assert_ne!(numbers(1), numbers(2));
+    }
+
+    #[test]
+    fn numbers_in_range() {
+        let mut rng = Rng::new(DEFAULT_SEED);
+
+        let rolls = (0..1_000).map(|_| rng.range(1..7)).collect::<Vec<_>>();
+        assert!(rolls.iter().all(|roll| (1..7).contains(roll)));
+        assert!((1..7).all(|n| rolls.contains(&n)));
+
+        assert_eq!(5, rng.range(5..6));
+        assert!(!rng.chance(0, 3));
+        assert!(rng.chance(3, 3));
+    }
+
+    #[test]
+    fn shuffle_keeps_items() {
+        let mut rng = Rng::new(DEFAULT_SEED);
+        let mut items = (0..20).collect::<Vec<_>>();
+
+        rng.shuffle(&mut items);
+        assert_ne!((0..20).collect::<Vec<_>>(), items);
+
+        items.sort();
+        assert_eq!((0..20).collect::<Vec<_>>(), items);
+    }
+
+    #[test]
+    fn options_from_args() {
+        let args =
+            |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(None, Options::from_args(&args(&["--bench"])).unwrap());
+        assert_eq!(
+            Some(Options {
+                size: 1_000,
+                seed: DEFAULT_SEED
+            }),
+            Options::from_args(&args(&["--generated", "1000"])).unwrap()
+        );
+        assert_eq!(
+            Some(Options { size: 10, seed: 7 }),
+            Options::from_args(&args(&["--generated=10", "--seed", "7"]))
+                .unwrap()
+        );
+        assert!(Options::from_args(&args(&["--seed", "7"])).is_err());
+        assert!(Options::from_args(&args(&["--generated", "x"])).is_err());
+    }
+}
@@ -0,0 +1,452 @@
+//! A rectangular grid of cells, as found in many puzzle inputs, indexed by
+//! [`Point`]s.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use anyhow::Context;
+
+use crate::point::{Direction, Direction8, Point, Vec2};
+
+/// A grid of `width` by `height` cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of the given size with each cell made from its position.
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut cell: impl FnMut(Point) -> T,
+    ) -> Self {
+        let cells = (0..width * height)
+            .map(|i| cell(Point::from_index(i, width)))
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// A grid of the rows, which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> anyhow::Result<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+
+        if let Some(i) = rows.iter().position(|row| row.len() != width) {
+            anyhow::bail!(
+                "row {} is {} wide, but row 1 is {width} wide",
+                i + 1,
+                rows[i].len()
+            );
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parse a grid with a cell for each character, naming the line and
+    /// column of any character that `cell` can't parse.
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Self> {
+        let rows = input
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| {
+                        cell(c).with_context(|| {
+                            format!(
+                                "invalid cell `{c}` at line {}, column {}",
+                                y + 1,
+                                x + 1
+                            )
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        point.to_index(self.width, self.height)
+    }
+
+    fn point(&self, index: usize) -> Point {
+        Point::from_index(index, self.width)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.index(point).is_some()
+    }
+
+    /// The cell at the point, or `None` if it is off the grid.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index(point).map(|i| &mut self.cells[i])
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.point(i), cell))
+    }
+
+    fn neighbours(
+        &self,
+        point: Point,
+        offsets: impl Iterator<Item = Vec2>,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        offsets.filter_map(move |offset| {
+            let neighbour = point + offset;
+            Some((neighbour, self.get(neighbour)?))
+        })
+    }
+
+    /// The cells above, right of, below and left of the point, leaving out
+    /// any that are off the grid.
+    pub fn neighbours4(
+        &self,
+        point: Point,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbours(
+            point,
+            Direction::ALL.into_iter().map(Direction::offset),
+        )
+    }
+
+    /// The cells around the point including the diagonals, clockwise from
+    /// above, leaving out any that are off the grid.
+    pub fn neighbours8(
+        &self,
+        point: Point,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbours(
+            point,
+            Direction8::ALL.into_iter().map(Direction8::offset),
+        )
+    }
+
+    /// The row `y`, if it is in the grid.
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height)
+            .then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Every row, from the top.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).filter_map(|y| self.row(y))
+    }
+
+    /// The cells of column `x` from the top, which is empty if the column
+    /// isn't in the grid.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// Every column, from the left.
+    pub fn columns(
+        &self,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The cells in the rectangle between two corners, inclusive, row by row.
+    /// Only the part of the rectangle that is in the grid is given.
+    pub fn region(
+        &self,
+        from: Point,
+        to: Point,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        // clipped first, so corners far off the grid cost nothing
+        let xs = from.x.min(to.x).max(0)
+            ..=from.x.max(to.x).min(self.width as i64 - 1);
+        let ys = from.y.min(to.y).max(0)
+            ..=from.y.max(to.y).min(self.height as i64 - 1);
+
+        ys.flat_map(move |y| xs.clone().map(move |x| Point::new(x, y)))
+            .map(|point| (point, &self[point]))
+    }
+
+    /// Where the first cell, row by row, that matches the predicate is.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.cells.iter().position(predicate).map(|i| self.point(i))
+    }
+
+    /// Where the first cell, row by row, that equals the value is.
+    pub fn position(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.find(|cell| cell == value)
+    }
+
+    /// A grid of the same size with every cell mapped.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// The grid flipped over its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, p.x)].clone()
+        })
+    }
+
+    /// The grid turned a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.height as i64;
+        Grid::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, height - 1 - p.x)].clone()
+        })
+    }
+
+    /// The grid turned a quarter turn anticlockwise.
+    pub fn rotate_anticlockwise(&self) -> Self {
+        let width = self.width as i64;
+        Grid::from_fn(self.height, self.width, |p| {
+            self[Point::new(width - 1 - p.y, p.x)].clone()
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    /// The cell at the point, which must be in the grid.
+    fn index(&self, point: Point) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!(
+                "{point:?} is outside the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(point) {
+            Some(cell) => cell,
+            None => panic!("{point:?} is outside the {width}x{height} grid"),
+        }
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, Ok)
+    }
+}
+
+/// The grid as text, with a line for each row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Grid<char> {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = grid("abc\ndef\n");
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!("abc\ndef", grid.to_string());
+
+        let digits =
+            Grid::parse("12\n34", |c| c.to_digit(10).context("not a digit"))
+                .unwrap();
+        assert_eq!(Some(&3), digits.get(Point::new(0, 1)));
+        assert_eq!("12\n34", digits.to_string());
+
+        let empty = Grid::<char>::from_str("").unwrap();
+        assert_eq!((0, 0), (empty.width(), empty.height()));
+        assert_eq!("", empty.to_string());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = Grid::<char>::from_str("abc\nde").unwrap_err();
+        assert_eq!("row 2 is 2 wide, but row 1 is 3 wide", err.to_string());
+
+        let err =
+            Grid::parse("12\n3x", |c| c.to_digit(10).context("not a digit"))
+                .unwrap_err();
+        assert_eq!("invalid cell `x` at line 2, column 2", err.to_string());
+    }
+
+    #[test]
+    fn get_off_the_edges() {
+        let grid = grid("ab\ncd");
+
+        assert_eq!(Some(&'d'), grid.get(Point::new(1, 1)));
+        assert_eq!('b', grid[Point::new(1, 0)]);
+        for point in [(-1, 0), (0, -1), (2, 0), (0, 2), (i64::MIN, i64::MAX)] {
+            let point = Point::new(point.0, point.1);
+            assert_eq!(None, grid.get(point));
+            assert!(!grid.contains(point));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the 2x2 grid")]
+    fn index_off_the_edge() {
+        let _ = grid("ab\ncd")[Point::new(2, 0)];
+    }
+
+    #[test]
+    fn set_cells() {
+        let mut grid = grid("ab\ncd");
+        grid[Point::new(0, 0)] = 'x';
+        *grid.get_mut(Point::new(1, 1)).unwrap() = 'y';
+
+        assert_eq!(None, grid.get_mut(Point::new(-1, 1)));
+        assert_eq!("xb\ncy", grid.to_string());
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = grid("abc\ndef\nghi");
+        let cells = |neighbours: Vec<(Point, &char)>| {
+            neighbours.into_iter().map(|(_, &c)| c).collect::<String>()
+        };
+
+        let centre = Point::new(1, 1);
+        assert_eq!("bfhd", cells(grid.neighbours4(centre).collect()));
+        assert_eq!("bcfihgda", cells(grid.neighbours8(centre).collect()));
+
+        let corner = Point::new(0, 0);
+        assert_eq!("bd", cells(grid.neighbours4(corner).collect()));
+        assert_eq!("bed", cells(grid.neighbours8(corner).collect()));
+
+        let (point, _) = grid.neighbours4(corner).next().unwrap();
+        assert_eq!(Point::new(1, 0), point);
+    }
+
+    #[test]
+    fn rows_columns_and_regions() {
+        let grid = grid("abc\ndef");
+
+        let rows = grid.rows().map(|r| r.iter().collect::<String>());
+        assert_eq!(vec!["abc", "def"], rows.collect::<Vec<_>>());
+        assert_eq!(None, grid.row(2));
+
+        let columns = grid.columns().map(|c| c.collect::<String>());
+        assert_eq!(vec!["ad", "be", "cf"], columns.collect::<Vec<_>>());
+        assert_eq!(0, grid.column(3).count());
+
+        let region = grid
+            .region(Point::new(-1, -1), Point::new(1, 0))
+            .map(|(_, &c)| c)
+            .collect::<String>();
+        assert_eq!("ab", region);
+
+        let region = grid.region(Point::new(2, 1), Point::new(1, 0));
+        assert_eq!(
+            vec![
+                (Point::new(1, 0), &'b'),
+                (Point::new(2, 0), &'c'),
+                (Point::new(1, 1), &'e'),
+                (Point::new(2, 1), &'f'),
+            ],
+            region.collect::<Vec<_>>()
+        );
+
+        let far = Point::new(i64::MIN, i64::MIN);
+        let region = grid.region(far, Point::new(i64::MAX, i64::MAX));
+        assert_eq!("abcdef", region.map(|(_, &c)| c).collect::<String>());
+        assert_eq!(0, grid.region(far, Point::new(-1, i64::MAX)).count());
+        assert_eq!(0, grid.region(Point::new(0, 2), Point::new(2, 9)).count());
+    }
+
+    #[test]
+    fn transpose_and_rotate() {
+        let grid = grid("abc\ndef");
+
+        assert_eq!("ad\nbe\ncf", grid.transpose().to_string());
+        assert_eq!("da\neb\nfc", grid.rotate_clockwise().to_string());
+        assert_eq!("cf\nbe\nad", grid.rotate_anticlockwise().to_string());
+
+        let turned = (0..4).fold(grid.clone(), |g, _| g.rotate_clockwise());
+        assert_eq!(grid, turned);
+        assert_eq!(grid, grid.rotate_clockwise().rotate_anticlockwise());
+    }
+
+    #[test]
+    fn find_cells() {
+        let grid = grid("ab*\n*c.");
+
+        assert_eq!(Some(Point::new(2, 0)), grid.position(&'*'));
+        assert_eq!(None, grid.position(&'#'));
+        assert_eq!(Some(Point::new(1, 1)), grid.find(|&c| c == 'c'));
+
+        let stars = grid.iter().filter(|(_, &c)| c == '*').map(|(p, _)| p);
+        assert_eq!(
+            vec![Point::new(2, 0), Point::new(0, 1)],
+            stars.collect::<Vec<_>>()
+        );
+        let stars = grid.map(|&c| u8::from(c == '*'));
+        assert_eq!("001\n100", stars.to_string());
+    }
+}
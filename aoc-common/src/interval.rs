@@ -0,0 +1,528 @@
+//! Intervals of `u64`s, and sets of them.
+//!
+//! Intervals include both ends so that every interval up to and including
+//! `u64::MAX` can be written, and are never empty: operations that could give
+//! an empty interval give an `Option` instead.
+
+use std::ops::RangeInclusive;
+
+/// The values from `start` to `end`, inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    start: u64,
+    end: u64,
+}
+
+/// The parts of an interval before, within and after another, as given by
+/// [`Interval::split`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Split {
+    pub before: Option<Interval>,
+    pub within: Option<Interval>,
+    pub after: Option<Interval>,
+}
+
+impl Interval {
+    /// Every `u64`.
+    pub const FULL: Interval = Interval {
+        start: 0,
+        end: u64::MAX,
+    };
+
+    /// The interval from `start` to `end` inclusive, or `None` if `end` is
+    /// before `start`.
+    pub fn new(start: u64, end: u64) -> Option<Self> {
+        (start <= end).then_some(Interval { start, end })
+    }
+
+    /// The `len` values from `start`, or `None` if there are none or they
+    /// would go past `u64::MAX`.
+    pub fn from_len(start: u64, len: u64) -> Option<Self> {
+        Interval::new(start, start.checked_add(len.checked_sub(1)?)?)
+    }
+
+    pub fn single(value: u64) -> Self {
+        Interval {
+            start: value,
+            end: value,
+        }
+    }
+
+    pub fn start(self) -> u64 {
+        self.start
+    }
+
+    /// The last value in the interval.
+    pub fn end(self) -> u64 {
+        self.end
+    }
+
+    /// How many values are in the interval, which only the full interval
+    /// has too many of to fit in a `u64`.
+    pub fn size(self) -> u128 {
+        u128::from(self.end - self.start) + 1
+    }
+
+    pub fn contains(self, value: u64) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether every value of `other` is in this interval.
+    pub fn contains_interval(self, other: Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether any value is in both intervals.
+    pub fn overlaps(self, other: Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Whether the intervals overlap or are next to each other, so that
+    /// together they make one interval.
+    fn touches(self, other: Interval) -> bool {
+        self.start <= other.end.saturating_add(1)
+            && other.start <= self.end.saturating_add(1)
+    }
+
+    /// The values in both intervals.
+    pub fn intersection(self, other: Interval) -> Option<Interval> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The values in either interval, if they make one interval.
+    pub fn union(self, other: Interval) -> Option<Interval> {
+        self.touches(other).then(|| Interval {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+
+    /// Split the interval into the values before, within and after `by`.
+    pub fn split(self, by: Interval) -> Split {
+        let before = (self.start < by.start)
+            .then(|| Interval::new(self.start, self.end.min(by.start - 1)))
+            .flatten();
+        let after = (self.end > by.end)
+            .then(|| Interval::new(self.start.max(by.end + 1), self.end))
+            .flatten();
+
+        Split {
+            before,
+            within: self.intersection(by),
+            after,
+        }
+    }
+
+    /// The values of this interval that aren't in `other`, which may be in
+    /// two pieces.
+    pub fn difference(self, other: Interval) -> impl Iterator<Item = Interval> {
+        let Split { before, after, .. } = self.split(other);
+        before.into_iter().chain(after)
+    }
+
+    /// The interval moved by the offset, or `None` if it would move past
+    /// either end of the `u64`s.
+    pub fn translate(self, offset: i64) -> Option<Interval> {
+        Some(Interval {
+            start: self.start.checked_add_signed(offset)?,
+            end: self.end.checked_add_signed(offset)?,
+        })
+    }
+}
+
+impl From<Interval> for RangeInclusive<u64> {
+    fn from(interval: Interval) -> Self {
+        interval.start..=interval.end
+    }
+}
+
+/// A set of values made of intervals.
+///
+/// The intervals are kept in order and coalesced, so that no two of them
+/// overlap or are next to each other.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    /// The intervals of the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// How many values are in the set.
+    pub fn size(&self) -> u128 {
+        self.iter().map(Interval::size).sum()
+    }
+
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<u64> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    /// The largest value in the set.
+    pub fn max(&self) -> Option<u64> {
+        self.intervals.last().map(|i| i.end)
+    }
+
+    /// The range of intervals that touch the interval.
+    fn touching(&self, interval: Interval) -> std::ops::Range<usize> {
+        let start = self
+            .intervals
+            .partition_point(|i| i.end.saturating_add(1) < interval.start);
+        let end = self
+            .intervals
+            .partition_point(|i| i.start <= interval.end.saturating_add(1));
+
+        start..end.max(start)
+    }
+
+    /// Add the interval's values to the set.
+    pub fn insert(&mut self, interval: Interval) {
+        let touching = self.touching(interval);
+        let merged = self.intervals[touching.clone()]
+            .iter()
+            .fold(interval, |merged, &i| merged.union(i).unwrap_or(merged));
+
+        self.intervals.splice(touching, [merged]);
+    }
+
+    /// Take the interval's values out of the set.
+    pub fn remove(&mut self, interval: Interval) {
+        let touching = self.touching(interval);
+        let left = self.intervals[touching.clone()]
+            .iter()
+            .flat_map(|i| i.difference(interval))
+            .collect::<Vec<_>>();
+
+        self.intervals.splice(touching, left);
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        let i = self.intervals.partition_point(|i| i.end < value);
+        self.intervals.get(i).is_some_and(|i| i.contains(value))
+    }
+
+    /// Whether every value of the interval is in the set.
+    pub fn contains_interval(&self, interval: Interval) -> bool {
+        let i = self.intervals.partition_point(|i| i.end < interval.start);
+        self.intervals
+            .get(i)
+            .is_some_and(|i| i.contains_interval(interval))
+    }
+
+    /// Whether any value of the interval is in the set.
+    pub fn overlaps(&self, interval: Interval) -> bool {
+        let i = self.intervals.partition_point(|i| i.end < interval.start);
+        self.intervals.get(i).is_some_and(|i| i.overlaps(interval))
+    }
+
+    /// The values in either set.
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut union = self.clone();
+        other.iter().for_each(|i| union.insert(i));
+        union
+    }
+
+    /// The values in both sets.
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        // the pieces come out in order and apart, so they are already
+        // coalesced
+        let intervals = self
+            .iter()
+            .flat_map(|a| {
+                let touching = other.touching(a);
+                other.intervals[touching]
+                    .iter()
+                    .filter_map(move |&b| a.intersection(b))
+            })
+            .collect();
+
+        IntervalSet { intervals }
+    }
+
+    /// The values of this set that aren't in `other`.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut difference = self.clone();
+        other.iter().for_each(|i| difference.remove(i));
+        difference
+    }
+
+    /// Every interval moved by the offset, or `None` if any would move past
+    /// either end of the `u64`s.
+    pub fn translate(&self, offset: i64) -> Option<IntervalSet> {
+        let intervals = self
+            .iter()
+            .map(|i| i.translate(offset))
+            .collect::<Option<_>>()?;
+
+        Some(IntervalSet { intervals })
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        IntervalSet {
+            intervals: vec![interval],
+        }
+    }
+}
+
+/// The set of every value in the intervals, which may overlap.
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut intervals = iter.into_iter().collect::<Vec<_>>();
+        intervals.sort();
+
+        let mut set = IntervalSet::new();
+        for interval in intervals {
+            match set.intervals.last_mut() {
+                Some(last) if last.touches(interval) => {
+                    *last = last.union(interval).unwrap_or(*last);
+                }
+                _ => set.intervals.push(interval),
+            }
+        }
+
+        set
+    }
+}
+
+impl Extend<Interval> for IntervalSet {
+    fn extend<I: IntoIterator<Item = Interval>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|i| self.insert(i));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX: u64 = u64::MAX;
+
+    /// The ends of the intervals that are tested, at both boundaries.
+    const ENDS: [u64; 6] = [0, 1, 2, MAX - 2, MAX - 1, MAX];
+
+    /// The values that the intervals are checked at: each end, the values
+    /// either side of it, and one far from both boundaries.
+    const PROBES: [u64; 9] =
+        [0, 1, 2, 3, MAX / 2, MAX - 3, MAX - 2, MAX - 1, MAX];
+
+    fn interval(start: u64, end: u64) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    /// Every interval between two of the ends.
+    fn intervals() -> Vec<Interval> {
+        ENDS.iter()
+            .flat_map(|&start| {
+                ENDS.iter()
+                    .filter_map(move |&end| Interval::new(start, end))
+            })
+            .collect()
+    }
+
+    /// Which of the probes are in the set.
+    fn members(contains: impl Fn(u64) -> bool) -> Vec<bool> {
+        PROBES.iter().map(|&p| contains(p)).collect()
+    }
+
+    fn set_members(set: &IntervalSet) -> Vec<bool> {
+        members(|p| set.iter().any(|i| i.contains(p)))
+    }
+
+    fn assert_coalesced(set: &IntervalSet) {
+        for pair in set.intervals.windows(2) {
+            assert!(
+                pair[0].end.saturating_add(1) < pair[1].start,
+                "{set:?} isn't coalesced"
+            );
+        }
+    }
+
+    #[test]
+    fn construction() {
+        assert_eq!(None, Interval::new(2, 1));
+        assert_eq!(Some(interval(2, 4)), Interval::from_len(2, 3));
+        assert_eq!(None, Interval::from_len(2, 0));
+        assert_eq!(Some(interval(MAX, MAX)), Interval::from_len(MAX, 1));
+        assert_eq!(None, Interval::from_len(MAX, 2));
+        assert_eq!(Some(interval(0, MAX - 1)), Interval::from_len(0, MAX));
+        assert_eq!(Some(Interval::FULL), Interval::new(0, MAX));
+
+        assert_eq!(1, Interval::single(MAX).size());
+        assert_eq!(1 << 64, Interval::FULL.size());
+        assert_eq!(2..=4, RangeInclusive::from(interval(2, 4)));
+    }
+
+    #[test]
+    fn pairs_of_intervals() {
+        for a in intervals() {
+            let in_a = members(|p| a.contains(p));
+            assert_eq!(u128::from(a.end - a.start) + 1, a.size());
+
+            for b in intervals() {
+                let in_b = members(|p| b.contains(p));
+                let both = |f: fn(bool, bool) -> bool| {
+                    in_a.iter().zip(&in_b).map(move |(&a, &b)| f(a, b))
+                };
+
+                let overlap = a.intersection(b);
+                assert_eq!(
+                    both(|a, b| a && b).collect::<Vec<_>>(),
+                    members(|p| overlap.is_some_and(|i| i.contains(p))),
+                    "{a:?} & {b:?}"
+                );
+                assert_eq!(overlap.is_some(), a.overlaps(b));
+                assert_eq!(
+                    a.contains_interval(b),
+                    both(|a, b| a || !b).all(|x| x)
+                );
+
+                let difference = a.difference(b).collect::<IntervalSet>();
+                assert_eq!(
+                    both(|a, b| a && !b).collect::<Vec<_>>(),
+                    set_members(&difference),
+                    "{a:?} - {b:?}"
+                );
+
+                let Split {
+                    before,
+                    within,
+                    after,
+                } = a.split(b);
+                assert_eq!(overlap, within);
+                assert!(before.is_none_or(|i| i.end < b.start));
+                assert!(after.is_none_or(|i| i.start > b.end));
+                let pieces = [before, within, after].into_iter().flatten();
+                assert_eq!(a.size(), pieces.map(Interval::size).sum::<u128>());
+
+                match a.union(b) {
+                    Some(union) => assert_eq!(
+                        both(|a, b| a || b).collect::<Vec<_>>(),
+                        members(|p| union.contains(p)),
+                        "{a:?} | {b:?}"
+                    ),
+                    None => assert!(
+                        a.end.saturating_add(1) < b.start
+                            || b.end.saturating_add(1) < a.start
+                    ),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn translate() {
+        let a = interval(1, 2);
+
+        assert_eq!(Some(interval(4, 5)), a.translate(3));
+        assert_eq!(Some(interval(0, 1)), a.translate(-1));
+        assert_eq!(None, a.translate(-2));
+        assert_eq!(
+            Some(interval(MAX - 1, MAX)),
+            interval(MAX - 3, MAX - 2).translate(2)
+        );
+        assert_eq!(None, interval(MAX - 3, MAX - 2).translate(3));
+        assert_eq!(None, Interval::FULL.translate(1));
+        assert_eq!(Some(Interval::FULL), Interval::FULL.translate(0));
+
+        let set = IntervalSet::from_iter([interval(0, 1), interval(5, 6)]);
+        assert_eq!(
+            Some(IntervalSet::from_iter([interval(2, 3), interval(7, 8)])),
+            set.translate(2)
+        );
+        assert_eq!(None, set.translate(-1));
+    }
+
+    #[test]
+    fn sets_coalesce() {
+        let set = IntervalSet::from_iter([
+            interval(5, 6),
+            interval(0, 1),
+            interval(2, 3),
+            interval(8, 8),
+            interval(6, 7),
+        ]);
+        assert_eq!(
+            vec![interval(0, 3), interval(5, 8)],
+            set.iter().collect::<Vec<_>>()
+        );
+
+        let mut set = IntervalSet::new();
+        set.extend([interval(MAX, MAX), interval(0, 0), interval(2, 2)]);
+        assert_eq!(3, set.size());
+        set.insert(interval(1, MAX - 1));
+        assert_eq!(IntervalSet::from(Interval::FULL), set);
+
+        set.remove(interval(1, MAX - 1));
+        assert_eq!(
+            vec![interval(0, 0), interval(MAX, MAX)],
+            set.iter().collect::<Vec<_>>()
+        );
+        assert_eq!((Some(0), Some(MAX)), (set.min(), set.max()));
+    }
+
+    #[test]
+    fn sets_of_intervals() {
+        // sets of up to two intervals, against sets of one
+        let sets = intervals()
+            .into_iter()
+            .flat_map(|a| {
+                intervals()
+                    .into_iter()
+                    .map(move |b| IntervalSet::from_iter([a, b]))
+            })
+            .collect::<Vec<_>>();
+
+        for a in &sets {
+            assert_coalesced(a);
+            let in_a = set_members(a);
+
+            for b in intervals() {
+                let in_b = members(|p| b.contains(p));
+                let both = |f: fn(bool, bool) -> bool| {
+                    in_a.iter()
+                        .zip(&in_b)
+                        .map(|(&a, &b)| f(a, b))
+                        .collect::<Vec<_>>()
+                };
+                let b_set = IntervalSet::from(b);
+
+                for (set, expected) in [
+                    (a.union(&b_set), both(|a, b| a || b)),
+                    (a.intersection(&b_set), both(|a, b| a && b)),
+                    (a.difference(&b_set), both(|a, b| a && !b)),
+                    (b_set.difference(a), both(|a, b| !a && b)),
+                ] {
+                    assert_coalesced(&set);
+                    assert_eq!(expected, set_members(&set), "{a:?}, {b:?}");
+                }
+
+                assert_eq!(
+                    both(|a, b| a && b).contains(&true),
+                    a.overlaps(b),
+                    "{a:?} overlaps {b:?}"
+                );
+                assert_eq!(
+                    !both(|a, b| !a && b).contains(&true),
+                    a.contains_interval(b),
+                    "{a:?} contains {b:?}"
+                );
+            }
+
+            for p in PROBES {
+                assert_eq!(a.iter().any(|i| i.contains(p)), a.contains(p));
+            }
+        }
+    }
+}
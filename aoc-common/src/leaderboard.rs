@@ -0,0 +1,385 @@
+//! Private leaderboards, read from the JSON export of the website's
+//! leaderboard page, and reports of them alongside our own solve times.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    path::Path,
+    time::Duration,
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::table::{self, Align};
+
+/// The export of a private leaderboard for one year.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    /// The year, such as `"2023"`.
+    pub event: String,
+    /// Every member, keyed by their id.
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Members can choose to be anonymous.
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u32,
+    /// When each star was earned, by day then part.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Star {
+    /// Unix timestamp of when the star was earned.
+    pub get_star_ts: i64,
+}
+
+impl Member {
+    /// The member's name, as the website shows it.
+    pub fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// How long after the day unlocked the member earned the part's star.
+    pub fn star_time(&self, year: u16, day: u8, part: u8) -> Option<Duration> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        let seconds = star.get_star_ts - unlock(year, day);
+
+        Some(Duration::from_secs(seconds.try_into().unwrap_or_default()))
+    }
+}
+
+/// Unix timestamp of when the day's puzzle unlocked, at midnight EST.
+fn unlock(year: u16, day: u8) -> i64 {
+    // days from 1970-01-01 to the day in December, from Howard Hinnant's
+    // `days_from_civil`, where the year starts in March
+    let year = i64::from(year);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * 9 + 2) / 5 + i64::from(day) - 1;
+    let day_of_era =
+        year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    days * 86_400 + 5 * 3_600
+}
+
+/// When a member earned the stars of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StarTimes {
+    pub day: u8,
+    pub member: String,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl StarTimes {
+    /// How long part 2 took after part 1.
+    pub fn delta(&self) -> Option<Duration> {
+        self.part2?.checked_sub(self.part1?)
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> anyhow::Result<Self> {
+        let leaderboard = serde_json::from_str::<Leaderboard>(json)?;
+        leaderboard.year()?;
+
+        Ok(leaderboard)
+    }
+
+    /// Read a leaderboard exported to a file.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let json = crate::read_file(path.to_owned())?;
+
+        Leaderboard::parse(&json).with_context(|| {
+            format!("invalid leaderboard `{}`", path.display())
+        })
+    }
+
+    pub fn year(&self) -> anyhow::Result<u16> {
+        self.event
+            .parse()
+            .with_context(|| format!("invalid event `{}`", self.event))
+    }
+
+    /// Every member from the highest score down, with ties broken by stars
+    /// then name.
+    pub fn standings(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by_key(|m| {
+            (std::cmp::Reverse((m.local_score, m.stars)), m.name())
+        });
+
+        members
+    }
+
+    /// The star times of every member with a star on a day, by day and
+    /// then from the fastest to finish the day.
+    pub fn star_times(&self) -> Vec<StarTimes> {
+        // the year was checked when the leaderboard was parsed
+        let year = self.year().unwrap_or_default();
+
+        let mut times = self
+            .members
+            .values()
+            .flat_map(|member| {
+                member.completion_day_level.keys().map(|&day| StarTimes {
+                    day,
+                    member: member.name(),
+                    part1: member.star_time(year, day, 1),
+                    part2: member.star_time(year, day, 2),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // members without part 2 go last
+        times.sort_by_key(|t| {
+            (t.day, t.part2.is_none(), t.part2, t.part1, t.member.clone())
+        });
+
+        times
+    }
+}
+
+/// A time as `h:mm:ss`.
+fn clock(time: Option<Duration>) -> String {
+    match time {
+        Some(time) => {
+            let seconds = time.as_secs();
+            format!(
+                "{}:{:02}:{:02}",
+                seconds / 3_600,
+                seconds / 60 % 60,
+                seconds % 60
+            )
+        }
+        None => String::from("-"),
+    }
+}
+
+/// A leaderboard alongside how long our own solutions take to run each day.
+///
+/// Displayed as a table of the standings followed by a table of star times,
+/// or written as CSV with [`Stats::csv`].
+pub struct Stats<'a> {
+    pub leaderboard: &'a Leaderboard,
+    /// How long each day we have solved took to run.
+    pub solve_times: BTreeMap<u8, Duration>,
+}
+
+impl Stats<'_> {
+    fn solve_time(&self, day: u8) -> Option<Duration> {
+        self.solve_times.get(&day).copied()
+    }
+
+    /// The star times as CSV, with times in seconds, except for solve times
+    /// which are in milliseconds.
+    pub fn csv(&self) -> String {
+        let seconds = |time: Option<Duration>| {
+            time.map_or(String::new(), |time| time.as_secs().to_string())
+        };
+
+        let mut csv = String::from("day,member,part1,part2,delta,solve_ms\n");
+        for times in self.leaderboard.star_times() {
+            let solve = self.solve_time(times.day).map_or(String::new(), |t| {
+                format!("{:.3}", t.as_secs_f64() * 1000.0)
+            });
+
+            csv.push_str(&format!(
+                "{},{},{},{},{},{solve}\n",
+                times.day,
+                csv_field(&times.member),
+                seconds(times.part1),
+                seconds(times.part2),
+                seconds(times.delta()),
+            ));
+        }
+
+        csv
+    }
+}
+
+/// Quote a CSV field if it needs to be.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+impl fmt::Display for Stats<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let standings = self
+            .leaderboard
+            .standings()
+            .into_iter()
+            .zip(1..)
+            .map(|(member, rank)| {
+                [
+                    format!("{rank})"),
+                    member.name(),
+                    member.local_score.to_string(),
+                    member.stars.to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        table::write(
+            f,
+            ["", "member", "score", "stars"],
+            [Align::Right, Align::Left, Align::Right, Align::Right],
+            &standings,
+        )?;
+
+        let times = self
+            .leaderboard
+            .star_times()
+            .into_iter()
+            .map(|times| {
+                [
+                    times.day.to_string(),
+                    clock(times.part1),
+                    clock(times.part2),
+                    clock(times.delta()),
+                    self.solve_time(times.day)
+                        .map_or("-".into(), |time| format!("{time:.2?}")),
+                    times.member,
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        writeln!(f)?;
+        table::write(
+            f,
+            ["day", "part 1", "part 2", "delta", "solve", "member"],
+            [
+                Align::Right,
+                Align::Right,
+                Align::Right,
+                Align::Right,
+                Align::Right,
+                Align::Left,
+            ],
+            &times,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEADERBOARD: &str = include_str!("../fixtures/leaderboard.json");
+
+    fn duration(h: u64, m: u64, s: u64) -> Option<Duration> {
+        Some(Duration::from_secs(h * 3_600 + m * 60 + s))
+    }
+
+    #[test]
+    fn unlock_times() {
+        assert_eq!(1_701_406_800, unlock(2023, 1));
+        assert_eq!(1_669_870_800, unlock(2022, 1));
+        assert_eq!(1_703_480_400, unlock(2023, 25));
+    }
+
+    #[test]
+    fn standings() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        assert_eq!(2023, leaderboard.year().unwrap());
+
+        let names = leaderboard
+            .standings()
+            .into_iter()
+            .map(Member::name)
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["Liam", "Ada, L.", "(anonymous user #1003)"], names);
+    }
+
+    #[test]
+    fn star_times() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let times = leaderboard.star_times();
+
+        assert_eq!(
+            vec![
+                StarTimes {
+                    day: 1,
+                    member: String::from("Liam"),
+                    part1: duration(0, 5, 0),
+                    part2: duration(0, 15, 0),
+                },
+                StarTimes {
+                    day: 1,
+                    member: String::from("Ada, L."),
+                    part1: duration(0, 4, 0),
+                    part2: duration(0, 30, 0),
+                },
+                StarTimes {
+                    day: 1,
+                    member: String::from("(anonymous user #1003)"),
+                    part1: duration(25, 0, 0),
+                    part2: None,
+                },
+                StarTimes {
+                    day: 2,
+                    member: String::from("Liam"),
+                    part1: duration(0, 10, 0),
+                    part2: duration(0, 25, 0),
+                },
+                StarTimes {
+                    day: 2,
+                    member: String::from("Ada, L."),
+                    part1: duration(1, 0, 0),
+                    part2: None,
+                },
+            ],
+            times
+        );
+        assert_eq!(duration(0, 26, 0), times[1].delta());
+        assert_eq!(None, times[2].delta());
+    }
+
+    #[test]
+    fn report() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let stats = Stats {
+            leaderboard: &leaderboard,
+            solve_times: BTreeMap::from([(1, Duration::from_micros(1_500))]),
+        };
+
+        let text = stats.to_string();
+        assert!(text.starts_with("    member                  score  stars\n"));
+        assert!(text.contains("1)  Liam                       10      4\n"));
+        assert!(
+            text.contains("  1   0:04:00  0:30:00  0:26:00  1.50ms  Ada, L.\n")
+        );
+        assert!(text.contains("  1  25:00:00        -        -  1.50ms  (anon"));
+        assert!(text.contains("  2   1:00:00        -        -       -  Ada"));
+
+        let csv = stats.csv();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!("day,member,part1,part2,delta,solve_ms", lines[0]);
+        assert_eq!("1,Liam,300,900,600,1.500", lines[1]);
+        assert_eq!("1,\"Ada, L.\",240,1800,1560,1.500", lines[2]);
+        assert_eq!("2,\"Ada, L.\",3600,,,", lines[5]);
+    }
+
+    #[test]
+    fn invalid_leaderboard() {
+        let err =
+            Leaderboard::parse(r#"{"event":"xmas","members":{}}"#).unwrap_err();
+        assert_eq!("invalid event `xmas`", err.to_string());
+        assert!(Leaderboard::parse("[]").is_err());
+    }
+}
@@ -0,0 +1,385 @@
+//! The harness shared by every year of Advent of Code: the [`Solution`]
+//! trait, the [`setup!`] macro, the registry of days and the helpers they use.
+//!
+//! Each year is a crate of `src/bin/dayN.rs` binaries whose `build.rs` calls
+//! [`build::register_days`], and whose library includes the generated
+//! registry:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/days.rs"));
+//! ```
+//!
+//! A day can also be written as plain functions with the [`aoc`] and
+//! [`aoc_example`] attributes instead of [`setup!`].
+
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+pub mod answers;
+pub mod bench;
+pub mod build;
+pub mod client;
+pub mod differential;
+pub mod generate;
+pub mod grid;
+pub mod interval;
+pub mod leaderboard;
+pub mod numbers;
+pub mod parallel;
+pub mod parser;
+pub mod point;
+pub mod puzzle;
+pub mod registry;
+pub mod report;
+pub mod scaffold;
+pub mod solution;
+pub mod watch;
+mod table;
+
+pub use aoc_macros::{aoc, aoc_example};
+pub use registry::{Day, Year};
+pub use solution::{Fails, Solution};
+
+/// Environment variable naming the directory that holds `<day>.txt` inputs.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Read the puzzle input for the given day.
+///
+/// The input is resolved in the following order:
+/// 1. the path given with `--input <path>`, or stdin with `--input -`
+/// 2. `<day>.txt` inside the directory named by `AOC_INPUT_DIR`
+/// 3. `input/<day>.txt` inside the day's crate directory
+pub fn read_input(day: &Day) -> anyhow::Result<String> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let input = flag_value(&args, "--input")?;
+
+    read_input_from(input, input_path(day), std::io::stdin())
+}
+
+/// Read the input named by `--input`, or the file at `default` without it.
+///
+/// Stdin is only read when asked for, since it may be a pipe that is never
+/// closed when the runner is started by another process.
+fn read_input_from(
+    input: Option<&str>,
+    default: PathBuf,
+    mut stdin: impl Read,
+) -> anyhow::Result<String> {
+    match input {
+        Some("-") => {
+            let mut buf = String::new();
+            stdin
+                .read_to_string(&mut buf)
+                .context("failed to read stdin")?;
+            Ok(buf)
+        }
+        Some(path) => read_file(path.into()),
+        None => read_file(default),
+    }
+}
+
+/// Path of the input file for the given day: `<day>.txt` inside the
+/// [`input_dir`] of its crate.
+pub fn input_path(day: &Day) -> PathBuf {
+    input_dir(day.root.as_ref()).join(format!("{}.txt", day.name))
+}
+
+/// The directory named by `AOC_INPUT_DIR`, or the `input` directory of the
+/// year's crate at `root`.
+pub fn input_dir(root: &Path) -> PathBuf {
+    std::env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root.join("input"))
+}
+
+/// Find the value of `<flag> <value>` (or `<flag>=<value>`) in the arguments.
+pub fn flag_value<'a>(
+    args: &'a [String],
+    flag: &str,
+) -> anyhow::Result<Option<&'a str>> {
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            let value = args
+                .next()
+                .with_context(|| format!("`{flag}` requires a value"))?;
+            return Ok(Some(value));
+        }
+
+        if let Some(value) =
+            arg.strip_prefix(flag).and_then(|v| v.strip_prefix('='))
+        {
+            return Ok(Some(value));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Parse the value of `<flag> <value>`, if the flag was given.
+pub fn parse_flag<T>(args: &[String], flag: &str) -> anyhow::Result<Option<T>>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    flag_value(args, flag)?
+        .map(|value| {
+            value
+                .parse()
+                .with_context(|| format!("invalid value for `{flag}`"))
+        })
+        .transpose()
+}
+
+/// Parse every line of the input, naming the line that fails to parse.
+pub fn parse_lines<T>(input: &str) -> anyhow::Result<Vec<T>>
+where
+    T: std::str::FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.parse::<T>().map_err(Into::into).with_context(|| {
+                format!("failed to parse line {}: `{line}`", i + 1)
+            })
+        })
+        .collect()
+}
+
+/// Read the input file at `path`, naming the path if it can't be read.
+pub fn read_file(path: PathBuf) -> anyhow::Result<String> {
+    std::fs::read_to_string(&path).with_context(|| {
+        format!("failed to read puzzle input `{}`", path.display())
+    })
+}
+
+/// Generate `main`, the registry entry and the tests for a day.
+///
+/// The year is the one registered by the crate's `build.rs` with
+/// [`build::register_days`]. The second argument is the type implementing
+/// [`Solution`] for the day, followed by a list of examples for each part.
+/// Every example becomes its own test, named after the part and the example
+/// (e.g. `part2::eightwothree`):
+///
+/// ```ignore
+/// aoc_common::setup! {
+///     "day8",
+///     Day8,
+///     Part1: [
+///         example: r"..." => 6,
+///         unreachable: r"..." => aoc_common::Fails("can't be reached"),
+///     ],
+///     Part2: [
+///         example: r"..." => 6,
+///     ],
+/// }
+/// ```
+///
+/// Expected answers may be integer or string literals, including multi-line
+/// raw strings for ASCII art answers, or [`Fails`] for an example that should
+/// give an error. When both parts share a single example it can be written as
+/// `Example: r"...", Part1: 8, Part2: 2286`.
+///
+/// The generated `main` solves the day's input, or benchmarks it when run
+/// with `--bench [--iterations <n>] [--warmup <n>]`, and uses an input from
+/// the day's [`Solution::generate`] instead with
+/// `--generated <size> [--seed <n>]`. Alongside the examples, a `real_input`
+/// test checks the real input against `answers.toml` whenever both are
+/// present, a `generated_input` test checks that generated inputs solve, and
+/// an `implementations_agree` test checks any alternative implementations of
+/// the parts against each other with the [`differential`] tests.
+#[macro_export]
+macro_rules! setup {
+    (
+        $lit:literal,
+        $solution:ty,
+        Part1: [$($n1:ident: $i1:expr => $e1:expr),* $(,)?],
+        Part2: [$($n2:ident: $i2:expr => $e2:expr),* $(,)?] $(,)?
+    ) => {
+        pub const DAY: $crate::registry::Day = $crate::registry::Day {
+            year: $crate::registry::number(env!(
+                "AOC_YEAR",
+                "the crate's build.rs must call `aoc_common::build::register_days`"
+            )),
+            day: $crate::registry::number($lit) as u8,
+            name: $lit,
+            root: env!("CARGO_MANIFEST_DIR"),
+            solve: $crate::solution::solve::<$solution>,
+            bench: $crate::bench::bench::<$solution>,
+            generate: $crate::generate::generate::<$solution>,
+        };
+
+        // unused when the day is included by the registry
+        #[allow(dead_code)]
+        fn main() -> anyhow::Result<()> {
+            DAY.main()
+        }
+
+        #[test]
+        fn real_input() {
+            $crate::answers::test_real_input(&DAY);
+        }
+
+        #[test]
+        fn generated_input() {
+            $crate::generate::test_generated(&DAY);
+        }
+
+        #[test]
+        fn implementations_agree() {
+            $crate::differential::test_implementations::<$solution>(&[
+                $($i1,)* $($i2,)*
+            ]);
+        }
+
+        #[cfg(test)]
+        #[allow(unused_imports)]
+        mod part1 {
+            use super::*;
+            use $crate::{solution::Expected, Solution};
+
+            $(
+                #[test]
+                fn $n1() {
+                    let answer = <$solution>::parse($crate::solution::example($i1))
+                        .and_then(|parsed| <$solution>::part1(&parsed));
+                    $e1.assert(answer);
+                }
+            )*
+        }
+
+        #[cfg(test)]
+        #[allow(unused_imports)]
+        mod part2 {
+            use super::*;
+            use $crate::{solution::Expected, Solution};
+
+            $(
+                #[test]
+                fn $n2() {
+                    let answer = <$solution>::parse($crate::solution::example($i2))
+                        .and_then(|parsed| <$solution>::part2(&parsed));
+                    $e2.assert(answer);
+                }
+            )*
+        }
+    };
+
+    ($lit:literal, $solution:ty, Example: $example:literal, Part1: $p1:literal, Part2: $p2:literal $(,)?) => {
+        $crate::setup! {
+            $lit,
+            $solution,
+            Part1: [example: $example => $p1],
+            Part2: [example: $example => $p2],
+        }
+    };
+
+    ($lit:literal, $solution:ty, Part1: $p1example:literal = $p1:literal, Part2: $p2example:literal = $p2:literal $(,)?) => {
+        $crate::setup! {
+            $lit,
+            $solution,
+            Part1: [example: $p1example => $p1],
+            Part2: [example: $p2example => $p2],
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn flag_value_forms() {
+        assert_eq!(None, flag_value(&args(&[]), "--input").unwrap());
+        assert_eq!(
+            Some("a.txt"),
+            flag_value(&args(&["--input", "a.txt"]), "--input").unwrap()
+        );
+        assert_eq!(
+            Some("b.txt"),
+            flag_value(&args(&["--input=b.txt"]), "--input").unwrap()
+        );
+        assert_eq!(
+            None,
+            flag_value(&args(&["--inputs=b.txt"]), "--input").unwrap()
+        );
+        assert!(flag_value(&args(&["--input"]), "--input").is_err());
+    }
+
+    /// Stdin that mustn't be read.
+    struct Untouched;
+
+    impl Read for Untouched {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            panic!("stdin was read");
+        }
+    }
+
+    #[test]
+    fn input_sources() {
+        let dir = std::env::temp_dir()
+            .join(format!("aoc-common-input-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("day1.txt");
+        std::fs::write(&file, "1 2 3\n").unwrap();
+        let missing = dir.join("day2.txt");
+
+        let input = read_input_from(Some("-"), missing.clone(), &b"4 5"[..]);
+        assert_eq!("4 5", input.unwrap());
+
+        let path = file.to_str().unwrap();
+        let input = read_input_from(Some(path), missing.clone(), Untouched);
+        assert_eq!("1 2 3\n", input.unwrap());
+        let input = read_input_from(None, file, Untouched);
+        assert_eq!("1 2 3\n", input.unwrap());
+
+        // stdin isn't a fallback for a missing input file
+        let err =
+            read_input_from(None, missing.clone(), Untouched).unwrap_err();
+        assert_eq!(
+            format!("failed to read puzzle input `{}`", missing.display()),
+            err.to_string()
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn parse_flag_values() {
+        assert_eq!(
+            Some(10),
+            parse_flag::<usize>(&args(&["--iterations", "10"]), "--iterations")
+                .unwrap()
+        );
+        assert!(parse_flag::<usize>(
+            &args(&["--iterations", "x"]),
+            "--iterations"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn parse_lines_names_line() {
+        assert_eq!(vec![1, 2], parse_lines::<u32>("1\n2").unwrap());
+
+        let err = parse_lines::<u32>("1\nx").unwrap_err();
+        assert_eq!("failed to parse line 2: `x`", err.to_string());
+    }
+
+    #[test]
+    fn missing_file_names_path() {
+        let err = read_file("does/not/exist.txt".into()).unwrap_err();
+        assert!(err.to_string().contains("does/not/exist.txt"));
+    }
+}
@@ -0,0 +1,239 @@
+//! Finding every integer in a piece of input, whatever is around them.
+//!
+//! ```
+//! use aoc_common::numbers;
+//!
+//! let found = numbers::find::<i64>("x=-3, y=14").values();
+//! assert_eq!(vec![-3, 14], found.collect::<Result<Vec<_>, _>>().unwrap());
+//!
+//! let found = numbers::find::<u32>("move 12 from 3");
+//! let spans = found.map(|n| n.unwrap().span).collect::<Vec<_>>();
+//! assert_eq!(vec![5..7, 13..14], spans);
+//! ```
+
+use std::{any, fmt, marker::PhantomData, ops::Range};
+
+/// An integer type that numbers can be read as.
+///
+/// For signed types a `-` right before the digits makes the number negative,
+/// while unsigned types treat it like any other character between numbers.
+pub trait Integer: Copy + 'static {
+    const SIGNED: bool;
+    const ZERO: Self;
+
+    /// The number with another digit on the end, moving away from zero in
+    /// the direction of its sign, or `None` if that doesn't fit.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty)*) => {
+        $(
+            impl Integer for $t {
+                const SIGNED: bool = <$t>::MIN != 0;
+                const ZERO: Self = 0;
+
+                fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                    let n = self.checked_mul(10)?;
+                    if negative {
+                        n.checked_sub(digit as $t)
+                    } else {
+                        n.checked_add(digit as $t)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+/// A number that was found, and the byte range of the input it was found in,
+/// including any `-`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number<T> {
+    pub value: T,
+    pub span: Range<usize>,
+}
+
+/// A number that was found but doesn't fit in the type it was read as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub span: Range<usize>,
+    pub type_name: &'static str,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the number at {}..{} doesn't fit in `{}`",
+            self.span.start, self.span.end, self.type_name
+        )
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// The numbers in some input, made by [`find`].
+#[derive(Debug, Clone)]
+pub struct Numbers<'a, T> {
+    input: &'a [u8],
+    position: usize,
+    integer: PhantomData<T>,
+}
+
+/// Every number in the input, in order, read as a `T`.
+pub fn find<T: Integer>(input: &(impl AsRef<[u8]> + ?Sized)) -> Numbers<'_, T> {
+    Numbers {
+        input: input.as_ref(),
+        position: 0,
+        integer: PhantomData,
+    }
+}
+
+impl<'a, T: Integer> Numbers<'a, T> {
+    /// Just the value of each number.
+    pub fn values(self) -> impl Iterator<Item = Result<T, Overflow>> + 'a {
+        self.map(|number| number.map(|n| n.value))
+    }
+}
+
+impl<T: Integer> Iterator for Numbers<'_, T> {
+    type Item = Result<Number<T>, Overflow>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.input[self.position..];
+        let digits =
+            self.position + rest.iter().position(u8::is_ascii_digit)?;
+
+        let negative =
+            T::SIGNED && digits > 0 && self.input[digits - 1] == b'-';
+        let start = if negative { digits - 1 } else { digits };
+
+        let rest = &self.input[digits..];
+        let end = digits
+            + rest
+                .iter()
+                .position(|b| !b.is_ascii_digit())
+                .unwrap_or(rest.len());
+        self.position = end;
+
+        let value = self.input[digits..end]
+            .iter()
+            .try_fold(T::ZERO, |n, d| n.push_digit(d - b'0', negative));
+
+        Some(match value {
+            Some(value) => Ok(Number {
+                value,
+                span: start..end,
+            }),
+            None => Err(Overflow {
+                span: start..end,
+                type_name: any::type_name::<T>(),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values<T: Integer>(input: &str) -> Vec<T> {
+        find(input).values().collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn finds_numbers_anywhere() {
+        assert_eq!(vec![1, 2, 3], values::<u32>("move 1 from 2 to 3"));
+        assert_eq!(vec![41, 48, 83], values::<u32>("Card 41:  48 | 83"));
+        assert_eq!(vec![0, 7, 0], values::<u8>("0 007 00"));
+        assert_eq!(Vec::<u32>::new(), values::<u32>(""));
+        assert_eq!(Vec::<u32>::new(), values::<u32>("no numbers - here"));
+    }
+
+    #[test]
+    fn signs_depend_on_the_type() {
+        assert_eq!(vec![2, 4, 6, 8], values::<u32>("2-4,6-8"));
+        assert_eq!(vec![2, -4, 6, -8], values::<i32>("2-4,6-8"));
+        assert_eq!(vec![-1, 3], values::<i64>("--1 - 3"));
+        assert_eq!(vec![0], values::<i64>("-0"));
+    }
+
+    #[test]
+    fn spans() {
+        let spans = |input: &str| {
+            find::<i32>(input)
+                .map(|n| n.unwrap().span)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(vec![0..2, 3..5, 6..8], spans("12 -3 45"));
+        assert_eq!(vec![2..5], spans("x=-42"));
+    }
+
+    #[test]
+    fn bytes() {
+        let found = find::<u16>(b"a1b22".as_slice());
+        let found = found.collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                Ok(Number {
+                    value: 1,
+                    span: 1..2
+                }),
+                Ok(Number {
+                    value: 22,
+                    span: 3..5
+                })
+            ],
+            found
+        );
+    }
+
+    #[test]
+    fn overflow_at_the_boundaries() {
+        // every number a little beyond the range of the type
+        for n in -300i64..300 {
+            let input = format!("x{n}y");
+
+            let found = find::<u8>(&input).values().next().unwrap();
+            match u8::try_from(n.abs()) {
+                Ok(n) => assert_eq!(Ok(n), found),
+                Err(_) => assert!(found.is_err(), "{input}"),
+            }
+
+            let found = find::<i8>(&input).values().next().unwrap();
+            match i8::try_from(n) {
+                Ok(n) => assert_eq!(Ok(n), found),
+                Err(_) => assert!(found.is_err(), "{input}"),
+            }
+        }
+
+        assert_eq!(vec![u64::MAX], values::<u64>("18446744073709551615"));
+        assert_eq!(vec![i64::MIN], values::<i64>("-9223372036854775808"));
+        assert_eq!(vec![i128::MIN], values::<i128>(&i128::MIN.to_string()));
+
+        let overflow = find::<u64>("a 18446744073709551616 b").next().unwrap();
+        assert_eq!(
+            Err(Overflow {
+                span: 2..22,
+                type_name: "u64"
+            }),
+            overflow
+        );
+        assert_eq!(
+            "the number at 2..22 doesn't fit in `u64`",
+            overflow.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn carries_on_after_overflow() {
+        let found = find::<u8>("300 2").values().collect::<Vec<_>>();
+        assert!(found[0].is_err());
+        assert_eq!(Ok(2), found[1]);
+    }
+}
@@ -0,0 +1,379 @@
+//! Solving many days at once, isolated from each other so that a day that
+//! panics or never finishes can't stop the rest.
+
+use std::{
+    any::Any,
+    cell::Cell,
+    fmt, panic,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Once,
+    },
+    thread,
+    time::Duration,
+};
+
+use rayon::prelude::*;
+
+use crate::{
+    registry::Day,
+    solution::{Answer, Run},
+    table::{self, Align},
+};
+
+/// How long a day may take before it is given up on, unless told otherwise.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// What happened when solving a day in isolation.
+#[derive(Debug)]
+pub enum Outcome {
+    /// The day finished, though its input or either part may have failed.
+    Finished(anyhow::Result<Run>),
+    /// The day panicked, with the panic's message.
+    Panicked(String),
+    /// The day was still running when its time was up.
+    TimedOut(Duration),
+}
+
+impl Outcome {
+    /// Whether both parts were solved.
+    pub fn passed(&self) -> bool {
+        match self {
+            Outcome::Finished(Ok(run)) => {
+                run.part1.is_ok() && run.part2.is_ok()
+            }
+            _ => false,
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        match self {
+            _ if self.passed() => "passed",
+            Outcome::Finished(_) => "failed",
+            Outcome::Panicked(_) => "panicked",
+            Outcome::TimedOut(_) => "timed out",
+        }
+    }
+
+    /// Time taken to parse the input and solve each part that was solved.
+    pub fn time(&self) -> Option<Duration> {
+        match self {
+            Outcome::Finished(Ok(run)) => Some(
+                [&run.part1, &run.part2]
+                    .into_iter()
+                    .flatten()
+                    .map(|answer| answer.time)
+                    .sum::<Duration>()
+                    + run.parse,
+            ),
+            _ => None,
+        }
+    }
+
+    /// Treat a panic or timeout as the day failing with an error.
+    pub fn into_run(self) -> anyhow::Result<Run> {
+        match self {
+            Outcome::Finished(run) => run,
+            Outcome::Panicked(message) => anyhow::bail!("panicked: {message}"),
+            Outcome::TimedOut(timeout) => {
+                anyhow::bail!("timed out after {timeout:.2?}")
+            }
+        }
+    }
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+thread_local! {
+    /// Whether panics on this thread go unprinted.
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Stop panics on the current thread from being printed, leaving every other
+/// thread to the panic hook that was already set.
+fn quiet_panics() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                hook(info);
+            }
+        }));
+    });
+
+    QUIET.with(|quiet| quiet.set(true));
+}
+
+/// Solve the day on its own thread, giving up on it after `timeout`.
+///
+/// A panic in the day isn't printed, since it is part of the [`Outcome`]
+/// instead. A day that times out can't be stopped, so its thread is left
+/// running in the background until the process exits.
+pub fn solve(day: &'static Day, input: String, timeout: Duration) -> Outcome {
+    let (tx, rx) = mpsc::channel();
+
+    let spawned = thread::Builder::new()
+        .name(format!("{} day {}", day.year, day.day))
+        .spawn(move || {
+            quiet_panics();
+            let result = panic::catch_unwind(|| (day.solve)(&input));
+            // nobody is listening any more if the day timed out
+            let _ = tx.send(result);
+        });
+
+    if let Err(e) = spawned {
+        return Outcome::Finished(Err(e.into()));
+    }
+
+    match rx.recv_timeout(timeout) {
+        Ok(Ok(run)) => Outcome::Finished(run),
+        Ok(Err(payload)) => Outcome::Panicked(panic_message(payload.as_ref())),
+        Err(RecvTimeoutError::Timeout) => Outcome::TimedOut(timeout),
+        Err(RecvTimeoutError::Disconnected) => {
+            Outcome::Panicked(String::from("the day's thread stopped"))
+        }
+    }
+}
+
+/// Solve every day concurrently, reading each day's input with `input`.
+pub fn solve_all<F>(
+    days: &[&'static Day],
+    input: F,
+    timeout: Duration,
+) -> Summary
+where
+    F: Fn(&Day) -> anyhow::Result<String> + Sync,
+{
+    let outcomes = days
+        .par_iter()
+        .map(|&day| {
+            let outcome = match input(day) {
+                Ok(input) => solve(day, input, timeout),
+                Err(e) => Outcome::Finished(Err(e)),
+            };
+            (day, outcome)
+        })
+        .collect();
+
+    Summary(outcomes)
+}
+
+/// The outcome of every day, displayed as a table followed by the reason
+/// each day that didn't pass failed.
+pub struct Summary(pub Vec<(&'static Day, Outcome)>);
+
+impl Summary {
+    /// How many days didn't pass.
+    pub fn failures(&self) -> usize {
+        self.0.iter().filter(|(_, o)| !o.passed()).count()
+    }
+}
+
+/// The first line of an answer, marking that there is more of it.
+fn cell(answer: &anyhow::Result<Answer>) -> String {
+    match answer {
+        Ok(answer) if answer.value.contains('\n') => {
+            format!("{}...", answer.value.lines().next().unwrap_or_default())
+        }
+        Ok(answer) => answer.value.clone(),
+        Err(_) => String::from("-"),
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self
+            .0
+            .iter()
+            .map(|(day, outcome)| {
+                let (part1, part2) = match outcome {
+                    Outcome::Finished(Ok(run)) => {
+                        (cell(&run.part1), cell(&run.part2))
+                    }
+                    _ => ("-".into(), "-".into()),
+                };
+                let time = match outcome {
+                    Outcome::TimedOut(timeout) => format!(">{timeout:.2?}"),
+                    _ => outcome
+                        .time()
+                        .map_or("-".into(), |time| format!("{time:.2?}")),
+                };
+
+                [
+                    day.day.to_string(),
+                    outcome.status().into(),
+                    part1,
+                    part2,
+                    time,
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        table::write(
+            f,
+            ["day", "status", "part 1", "part 2", "time"],
+            [
+                Align::Right,
+                Align::Left,
+                Align::Left,
+                Align::Left,
+                Align::Right,
+            ],
+            &rows,
+        )?;
+
+        for (day, outcome) in &self.0 {
+            let error = match outcome {
+                Outcome::Finished(Err(e)) => format!("{e:#}"),
+                Outcome::Finished(Ok(run)) => {
+                    match [&run.part1, &run.part2]
+                        .into_iter()
+                        .find_map(|p| p.as_ref().err())
+                    {
+                        Some(e) => format!("{e:#}"),
+                        None => continue,
+                    }
+                }
+                Outcome::Panicked(message) => format!("panicked: {message}"),
+                Outcome::TimedOut(timeout) => {
+                    format!("timed out after {timeout:.2?}")
+                }
+            };
+
+            writeln!(f, "\n{} day {}: {error}", day.year, day.day)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bench, generate, solution, Solution};
+
+    macro_rules! day {
+        ($name:ident, $day:literal, $part1:expr) => {
+            struct $name;
+
+            impl Solution for $name {
+                type Parsed = ();
+                type Answer1 = u32;
+                type Answer2 = &'static str;
+
+                fn parse(_: &str) -> anyhow::Result<()> {
+                    Ok(())
+                }
+
+                fn part1(_: &()) -> anyhow::Result<u32> {
+                    $part1
+                }
+
+                fn part2(_: &()) -> anyhow::Result<&'static str> {
+                    Ok("#.\n.#")
+                }
+            }
+
+            impl $name {
+                const DAY: Day = Day {
+                    year: 2023,
+                    day: $day,
+                    name: concat!("day", $day),
+                    root: "",
+                    solve: solution::solve::<$name>,
+                    bench: bench::bench::<$name>,
+                    generate: generate::generate::<$name>,
+                };
+            }
+        };
+    }
+
+    day!(Passes, 1, Ok(6440));
+    day!(Fails, 2, Err(anyhow::anyhow!("bad hand")));
+    day!(Panics, 3, panic!("not yet"));
+    day!(Hangs, 4, {
+        thread::sleep(Duration::from_secs(60));
+        Ok(0)
+    });
+
+    static DAYS: &[&Day] =
+        &[&Passes::DAY, &Fails::DAY, &Panics::DAY, &Hangs::DAY];
+
+    #[test]
+    fn isolates_each_day() {
+        let summary =
+            solve_all(DAYS, |_| Ok(String::new()), Duration::from_millis(200));
+
+        let statuses = summary
+            .0
+            .iter()
+            .map(|(day, outcome)| (day.day, outcome.status()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (1, "passed"),
+                (2, "failed"),
+                (3, "panicked"),
+                (4, "timed out")
+            ],
+            statuses
+        );
+        assert_eq!(3, summary.failures());
+
+        let table = summary.to_string();
+        assert!(table.contains("6440"));
+        assert!(table.contains("#...")); // multi-line answers are cut short
+        assert!(table.contains("2023 day 2: failed to solve part 1: bad hand"));
+        assert!(table.contains("2023 day 3: panicked: not yet"));
+        assert!(table.contains("2023 day 4: timed out after 200.00ms"));
+    }
+
+    #[test]
+    fn missing_input_fails() {
+        let summary = solve_all(
+            &[&Passes::DAY],
+            |_| Err(anyhow::anyhow!("no input")),
+            DEFAULT_TIMEOUT,
+        );
+
+        assert_eq!(1, summary.failures());
+        assert!(summary.to_string().contains("2023 day 1: no input"));
+    }
+
+    #[test]
+    fn only_day_threads_are_quiet() {
+        solve_all(&[&Panics::DAY], |_| Ok(String::new()), DEFAULT_TIMEOUT);
+
+        // other threads keep printing their panics
+        assert!(!QUIET.with(Cell::get));
+        thread::spawn(|| {
+            assert!(!QUIET.with(Cell::get));
+            quiet_panics();
+            assert!(QUIET.with(Cell::get));
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn outcome_into_run() {
+        let err = Outcome::TimedOut(Duration::from_secs(1))
+            .into_run()
+            .unwrap_err();
+        assert_eq!("timed out after 1.00s", err.to_string());
+
+        let err = Outcome::Panicked(String::from("oops"))
+            .into_run()
+            .unwrap_err();
+        assert_eq!("panicked: oops", err.to_string());
+    }
+}
@@ -0,0 +1,557 @@
+//! Small parser combinators for puzzle inputs.
+//!
+//! A parser is anything that implements [`Parser`]. The functions here make
+//! the parsers that inputs are usually built from, [`from_fn`] makes one from
+//! a closure, and the methods of [`Parser`] combine them. A parser can be
+//! used more than once by reference. [`parse_all`] runs a parser over a whole
+//! input, and says where it failed with a [`ParseError`]:
+//!
+//! ```
+//! use aoc_common::parser::{self, literal, tuple, unsigned, Parser};
+//!
+//! let node = literal("AAA = ").ignore_then(tuple(unsigned(), unsigned()));
+//! assert_eq!((1, 2), parser::parse_all(&node, "AAA = (1, 2)").unwrap());
+//!
+//! let err = parser::parse_all(&node, "AAA = (1 2)").unwrap_err();
+//! assert_eq!((1, 9), (err.line, err.column));
+//! assert_eq!(
+//!     "expected `,` at line 1, column 9:\n1 | AAA = (1 2)\n  |         ^",
+//!     err.to_string()
+//! );
+//! ```
+
+use std::{any, fmt, str::FromStr};
+
+/// Why a parser failed: what it expected, and how much of the input was left
+/// where it failed, which [`ParseError`] turns into a line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub expected: String,
+    pub remaining: usize,
+}
+
+impl Failure {
+    /// A failure at the start of `rest`, the input that was left.
+    pub fn new(expected: impl Into<String>, rest: &str) -> Self {
+        Failure {
+            expected: expected.into(),
+            remaining: rest.len(),
+        }
+    }
+}
+
+/// What running a parser gives: the value and the rest of the input after
+/// it, or why it failed.
+pub type Step<'a, T> = Result<(T, &'a str), Failure>;
+
+/// Something that parses a `T` off the start of the input.
+pub trait Parser<'a, T>: Sized {
+    fn parse(&self, input: &'a str) -> Step<'a, T>;
+
+    /// Turn the value into another.
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+        from_fn(move |input| {
+            let (value, rest) = self.parse(input)?;
+            Ok((f(value), rest))
+        })
+    }
+
+    /// Turn the value into another, failing where the value starts if `f`
+    /// gives `None`, as a value that isn't what was `expected`.
+    fn try_map<U>(
+        self,
+        expected: &'static str,
+        f: impl Fn(T) -> Option<U>,
+    ) -> impl Parser<'a, U> {
+        from_fn(move |input| {
+            let (value, rest) = self.parse(input)?;
+            match f(value) {
+                Some(value) => Ok((value, rest)),
+                None => Err(Failure::new(expected, input)),
+            }
+        })
+    }
+
+    /// Parse `next` after this, giving both values.
+    fn then<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, (T, U)> {
+        from_fn(move |input| {
+            let (a, rest) = self.parse(input)?;
+            let (b, rest) = next.parse(rest)?;
+            Ok(((a, b), rest))
+        })
+    }
+
+    /// Parse `next` after this, keeping only this value.
+    fn then_ignore<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, T> {
+        self.then(next).map(|(a, _)| a)
+    }
+
+    /// Parse `next` after this, keeping only its value.
+    fn ignore_then<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, U> {
+        self.then(next).map(|(_, b)| b)
+    }
+
+    /// Parse `other` instead when this fails.
+    ///
+    /// When both fail, the failure that got furthest into the input is the
+    /// one given, since it is most likely the one that was meant.
+    fn or(self, other: impl Parser<'a, T>) -> impl Parser<'a, T> {
+        from_fn(move |input| {
+            let a = match self.parse(input) {
+                Ok(step) => return Ok(step),
+                Err(a) => a,
+            };
+            let b = match other.parse(input) {
+                Ok(step) => return Ok(step),
+                Err(b) => b,
+            };
+
+            Err(match a.remaining.cmp(&b.remaining) {
+                std::cmp::Ordering::Less => a,
+                std::cmp::Ordering::Greater => b,
+                std::cmp::Ordering::Equal => Failure {
+                    expected: format!("{} or {}", a.expected, b.expected),
+                    ..a
+                },
+            })
+        })
+    }
+}
+
+impl<'a, T, P: Parser<'a, T>> Parser<'a, T> for &P {
+    fn parse(&self, input: &'a str) -> Step<'a, T> {
+        (**self).parse(input)
+    }
+}
+
+/// A parser made by [`from_fn`].
+#[derive(Debug, Clone, Copy)]
+pub struct FromFn<F>(F);
+
+impl<'a, T, F> Parser<'a, T> for FromFn<F>
+where
+    F: Fn(&'a str) -> Step<'a, T>,
+{
+    fn parse(&self, input: &'a str) -> Step<'a, T> {
+        (self.0)(input)
+    }
+}
+
+/// Make a parser out of a closure.
+pub fn from_fn<'a, T, F>(f: F) -> FromFn<F>
+where
+    F: Fn(&'a str) -> Step<'a, T>,
+{
+    FromFn(f)
+}
+
+/// Exactly the given text.
+pub fn literal<'a>(text: &'static str) -> impl Parser<'a, &'a str> {
+    from_fn(move |input: &'a str| match input.strip_prefix(text) {
+        Some(rest) => Ok((&input[..text.len()], rest)),
+        None => {
+            let expected = format!("`{}`", text.escape_debug());
+            Err(Failure::new(expected, input))
+        }
+    })
+}
+
+/// The longest run of characters that match the predicate, which must be
+/// at least one character, described as `what` when there isn't one.
+pub fn take_while1<'a>(
+    what: &'static str,
+    predicate: impl Fn(char) -> bool,
+) -> impl Parser<'a, &'a str> {
+    from_fn(move |input: &'a str| {
+        let end = input.find(|c| !predicate(c)).unwrap_or(input.len());
+        if end == 0 {
+            return Err(Failure::new(what, input));
+        }
+
+        Ok(input.split_at(end))
+    })
+}
+
+/// Any number of spaces and tabs, including none.
+pub fn spaces<'a>() -> impl Parser<'a, &'a str> {
+    from_fn(|input: &'a str| {
+        let end = input.find(|c| c != ' ' && c != '\t').unwrap_or(input.len());
+        Ok(input.split_at(end))
+    })
+}
+
+/// An unsigned integer in decimal.
+pub fn unsigned<'a, T: FromStr>() -> impl Parser<'a, T> {
+    integer(take_while1("an unsigned integer", |c| c.is_ascii_digit()))
+}
+
+/// A decimal integer, which may start with `-` or `+`.
+pub fn signed<'a, T: FromStr>() -> impl Parser<'a, T> {
+    let sign = optional(literal("-").or(literal("+")));
+    let number = sign.then(take_while1("an integer", |c| c.is_ascii_digit()));
+
+    integer(from_fn(move |input: &'a str| {
+        let (_, rest) = number.parse(input)?;
+        Ok((&input[..input.len() - rest.len()], rest))
+    }))
+}
+
+/// Parse the text of an integer, failing where it starts if it doesn't fit
+/// in a `T`.
+fn integer<'a, T: FromStr>(
+    digits: impl Parser<'a, &'a str>,
+) -> impl Parser<'a, T> {
+    from_fn(move |input| {
+        let (text, rest) = digits.parse(input)?;
+        match text.parse() {
+            Ok(n) => Ok((n, rest)),
+            Err(_) => Err(Failure::new(
+                format!("an integer that fits in `{}`", any::type_name::<T>()),
+                input,
+            )),
+        }
+    })
+}
+
+/// The value of the parser, or `None` without using any input if it fails.
+pub fn optional<'a, T>(
+    parser: impl Parser<'a, T>,
+) -> impl Parser<'a, Option<T>> {
+    from_fn(move |input| match parser.parse(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(_) => Ok((None, input)),
+    })
+}
+
+/// One or more items with a separator between each.
+///
+/// The list ends at the first separator that isn't followed by an item,
+/// leaving that separator for whatever comes after the list. An item that
+/// fails partway through is an error though, so that the failure is reported
+/// where it happened rather than as unexpected input after the list.
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    from_fn(move |input| {
+        let (first, mut rest) = item.parse(input)?;
+        let mut items = vec![first];
+
+        while let Ok((_, after)) = separator.parse(rest) {
+            match item.parse(after) {
+                Ok((next, after)) => {
+                    items.push(next);
+                    rest = after;
+                }
+                Err(failure) if failure.remaining < after.len() => {
+                    return Err(failure)
+                }
+                Err(_) => break,
+            }
+        }
+
+        Ok((items, rest))
+    })
+}
+
+/// One or more items separated by spaces or tabs, e.g. `41 48  83`.
+pub fn list<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    separated(item, take_while1("a space", |c| c == ' ' || c == '\t'))
+}
+
+/// One or more items, each on its own line.
+pub fn lines<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    separated(item, literal("\n"))
+}
+
+/// One or more sections separated by blank lines.
+pub fn sections<'a, T>(section: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    separated(
+        section,
+        literal("\n\n").then(optional(take_while1("", |c| c == '\n'))),
+    )
+}
+
+/// A `key: value` pair, e.g. `Card 1: 41 48`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<'a, K>,
+    value: impl Parser<'a, V>,
+) -> impl Parser<'a, (K, V)> {
+    key.then_ignore(literal(":"))
+        .then_ignore(spaces())
+        .then(value)
+}
+
+/// A pair in brackets, e.g. `(BBB, CCC)`.
+pub fn tuple<'a, A, B>(
+    a: impl Parser<'a, A>,
+    b: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    literal("(")
+        .ignore_then(a)
+        .then_ignore(literal(","))
+        .then_ignore(spaces())
+        .then(b)
+        .then_ignore(literal(")"))
+}
+
+/// Run the parser over the whole input, which may only have whitespace left
+/// over at the end.
+pub fn parse_all<'a, T>(
+    parser: impl Parser<'a, T>,
+    input: &'a str,
+) -> Result<T, ParseError> {
+    let (value, rest) = parser
+        .parse(input)
+        .map_err(|failure| ParseError::new(input, failure))?;
+
+    let rest = rest.trim_end();
+    if !rest.is_empty() {
+        let failure = Failure::new("the end of the input", rest);
+        return Err(ParseError::new(input, failure));
+    }
+
+    Ok(value)
+}
+
+/// A [`Failure`] located in the input it happened in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub expected: String,
+    /// The line and column of the failure, counting from 1.
+    pub line: usize,
+    pub column: usize,
+    /// The whole line the failure is on.
+    pub source_line: String,
+}
+
+impl ParseError {
+    pub fn new(input: &str, failure: Failure) -> Self {
+        let mut offset = input.len().saturating_sub(failure.remaining);
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        ParseError {
+            expected: failure.expected,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            source_line: input[line_start..line_end].to_owned(),
+        }
+    }
+
+    /// The line that failed to parse, with a caret under the column where it
+    /// failed.
+    pub fn render(&self) -> String {
+        let gutter = self.line.to_string();
+
+        format!(
+            "{gutter} | {}\n{} | {}^",
+            self.source_line,
+            " ".repeat(gutter.len()),
+            " ".repeat(self.column - 1)
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} at line {}, column {}:\n{}",
+            self.expected,
+            self.line,
+            self.column,
+            self.render()
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse<'a, T>(
+        parser: impl Parser<'a, T>,
+        input: &'a str,
+    ) -> Result<T, String> {
+        parse_all(parser, input).map_err(|e| {
+            format!(
+                "expected {} at line {}, column {}",
+                e.expected, e.line, e.column
+            )
+        })
+    }
+
+    #[test]
+    fn literals() {
+        assert_eq!(Ok(("ab", "c")), literal("ab").parse("abc"));
+        assert_eq!(
+            Err(String::from("expected `ab` at line 1, column 1")),
+            parse(literal("ab"), "ac")
+        );
+        assert_eq!(
+            Err(String::from("expected `\\n\\n` at line 1, column 2")),
+            parse(literal("a").then(literal("\n\n")), "a\nb")
+        );
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(Ok(42u32), parse(unsigned(), "42"));
+        assert_eq!(Ok(-42i64), parse(signed(), "-42"));
+        assert_eq!(Ok(42i64), parse(signed(), "+42"));
+        assert_eq!(Ok(u64::MAX), parse(unsigned(), "18446744073709551615"));
+        assert_eq!(Ok(i8::MIN), parse(signed(), "-128"));
+
+        assert_eq!(
+            Err(String::from(
+                "expected an unsigned integer at line 1, column 1"
+            )),
+            parse(unsigned::<u32>(), "-1")
+        );
+        assert_eq!(
+            Err(String::from("expected an integer at line 1, column 2")),
+            parse(signed::<i32>(), "-x")
+        );
+        assert_eq!(
+            Err(String::from(
+                "expected an integer that fits in `u8` at line 1, column 1"
+            )),
+            parse(unsigned::<u8>(), "256")
+        );
+    }
+
+    #[test]
+    fn lists() {
+        assert_eq!(
+            Ok(vec![41, 48, 83]),
+            parse(list(unsigned::<u32>()), "41 48  83")
+        );
+        assert_eq!(
+            Ok((vec![1, 2], vec![3])),
+            parse(
+                list(unsigned::<u32>())
+                    .then_ignore(literal(" | "))
+                    .then(list(unsigned())),
+                "1 2 | 3"
+            )
+        );
+        assert_eq!(
+            Ok(vec![-1, 2]),
+            parse(separated(signed::<i32>(), literal(",")), "-1,2")
+        );
+        assert_eq!(
+            Err(String::from(
+                "expected the end of the input at line 1, column 4"
+            )),
+            parse(list(unsigned::<u32>()), "1 2 x")
+        );
+    }
+
+    #[test]
+    fn lines_and_sections() {
+        let section = lines(list(unsigned::<u32>()));
+
+        assert_eq!(
+            Ok(vec![vec![vec![1, 2], vec![3]], vec![vec![4]]]),
+            parse(sections(section), "1 2\n3\n\n\n4\n")
+        );
+    }
+
+    #[test]
+    fn key_values_and_tuples() {
+        let name = take_while1("a name", |c| c.is_ascii_alphanumeric());
+        let node = (&name)
+            .then_ignore(literal(" = "))
+            .then(tuple(&name, &name));
+        assert_eq!(
+            Ok(("AAA", ("BBB", "CCC"))),
+            parse(node, "AAA = (BBB, CCC)")
+        );
+
+        let seeds = key_value(literal("seeds"), list(unsigned::<u64>()));
+        assert_eq!(Ok(("seeds", vec![79, 14])), parse(seeds, "seeds:  79 14"));
+    }
+
+    #[test]
+    fn checked_values() {
+        let even = unsigned::<u32>()
+            .try_map("an even number", |n| (n % 2 == 0).then_some(n));
+        let pair = (&even).then_ignore(literal(",")).then(&even);
+
+        assert_eq!(Ok((2, 4)), parse(&pair, "2,4"));
+        assert_eq!(
+            Err(String::from("expected an even number at line 1, column 3")),
+            parse(&pair, "2,13")
+        );
+    }
+
+    #[test]
+    fn alternatives() {
+        let colour = literal("red").or(literal("green")).or(literal("blue"));
+        assert_eq!(Ok("blue"), parse(&colour, "blue"));
+        assert_eq!(
+            Err(String::from(
+                "expected `red` or `green` or `blue` at line 1, column 1"
+            )),
+            parse(&colour, "pink")
+        );
+
+        // the failure that got further is the one that's reported
+        let count = unsigned::<u32>().then_ignore(literal(" red"));
+        let either = count.or(literal("none").map(|_| 0));
+        assert_eq!(
+            Err(String::from("expected ` red` at line 1, column 2")),
+            parse(either, "3 blue")
+        );
+    }
+
+    #[test]
+    fn errors_are_located() {
+        let input = "seeds: 1 2\n\nsoil: 3 x";
+        let section = key_value(
+            take_while1("a name", |c| c.is_ascii_alphabetic()),
+            list(unsigned::<u32>()),
+        );
+        let section = section.then_ignore(literal(";"));
+
+        let err = parse_all(sections(section), input).unwrap_err();
+        assert_eq!((1, 11), (err.line, err.column));
+
+        // the second line fails partway through, rather than ending the list
+        let pair = unsigned::<u32>()
+            .then_ignore(literal("-"))
+            .then(unsigned::<u32>());
+        assert_eq!(
+            Err(String::from("expected `-` at line 2, column 2")),
+            parse(lines(&pair), "1-2\n3x4")
+        );
+
+        let err = parse_all(lines(&pair), "x-2\n3-4").unwrap_err();
+        assert_eq!((1, 1), (err.line, err.column));
+
+        // a line that runs out at the end of the input
+        let err = parse_all(lines(&pair), "1-2\n3-").unwrap_err();
+        assert_eq!((2, 3), (err.line, err.column));
+        assert_eq!("an unsigned integer", err.expected);
+
+        let err = parse_all(lines(list(unsigned::<u32>())), "1 2\n3 4 x\n5")
+            .unwrap_err();
+        assert_eq!((2, 4), (err.line, err.column));
+        assert_eq!("3 4 x", err.source_line);
+        assert_eq!("2 | 3 4 x\n  |    ^", err.render());
+        assert_eq!(
+            "expected the end of the input at line 2, column 4:\n\
+             2 | 3 4 x\n  |    ^",
+            err.to_string()
+        );
+    }
+}
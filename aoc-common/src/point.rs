@@ -0,0 +1,330 @@
+//! Positions and offsets on a grid, and the directions between them.
+//!
+//! Everything is signed, so stepping off the top or left edge of a grid gives
+//! a position that just isn't in it rather than underflowing.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position: `x` is the column and `y` is the row, counting from the top
+/// left, so `y` grows downwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The offset from one [`Point`] to another.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    /// The point at an index into cells stored row by row, `width` to a row.
+    pub fn from_index(index: usize, width: usize) -> Self {
+        Point::new((index % width) as i64, (index / width) as i64)
+    }
+
+    /// The index of the point into cells stored row by row, or `None` if it
+    /// is outside a grid of `width` by `height`.
+    pub fn to_index(self, width: usize, height: usize) -> Option<usize> {
+        let x = usize::try_from(self.x).ok().filter(|&x| x < width)?;
+        let y = usize::try_from(self.y).ok().filter(|&y| y < height)?;
+
+        Some(y * width + x)
+    }
+
+    /// The number of steps between the points moving only orthogonally.
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The number of steps between the points moving diagonally as well, so
+    /// that every point around this one is 1 away.
+    pub fn chebyshev(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The neighbouring point in the direction.
+    pub fn step(self, direction: impl Into<Direction8>) -> Point {
+        self + direction.into().offset()
+    }
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl Add<Vec2> for Point {
+    type Output = Point;
+
+    fn add(self, offset: Vec2) -> Point {
+        Point::new(self.x + offset.x, self.y + offset.y)
+    }
+}
+
+impl Sub<Vec2> for Point {
+    type Output = Point;
+
+    fn sub(self, offset: Vec2) -> Point {
+        Point::new(self.x - offset.x, self.y - offset.y)
+    }
+}
+
+/// The offset that takes the right point to the left one.
+impl Sub for Point {
+    type Output = Vec2;
+
+    fn sub(self, other: Point) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl AddAssign<Vec2> for Point {
+    fn add_assign(&mut self, offset: Vec2) {
+        *self = *self + offset;
+    }
+}
+
+impl SubAssign<Vec2> for Point {
+    fn sub_assign(&mut self, offset: Vec2) {
+        *self = *self - offset;
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, other: Vec2) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, n: i64) -> Vec2 {
+        Vec2::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+/// One of the 4 orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The direction a quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    /// The direction a quarter turn anticlockwise.
+    pub fn turn_left(self) -> Self {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The offset of a single step in the direction.
+    pub fn offset(self) -> Vec2 {
+        Direction8::from(self).offset()
+    }
+}
+
+/// One of the 4 orthogonal or 4 diagonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from up.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// The direction an eighth of a turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Direction8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// The direction an eighth of a turn anticlockwise.
+    pub fn turn_left(self) -> Self {
+        Direction8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+
+    /// The offset of a single step in the direction.
+    pub fn offset(self) -> Vec2 {
+        match self {
+            Direction8::Up => Vec2::new(0, -1),
+            Direction8::UpRight => Vec2::new(1, -1),
+            Direction8::Right => Vec2::new(1, 0),
+            Direction8::DownRight => Vec2::new(1, 1),
+            Direction8::Down => Vec2::new(0, 1),
+            Direction8::DownLeft => Vec2::new(-1, 1),
+            Direction8::Left => Vec2::new(-1, 0),
+            Direction8::UpLeft => Vec2::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Direction8::ALL[direction as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 4);
+
+        assert_eq!(Vec2::new(4, -6), a - b);
+        assert_eq!(a, b + (a - b));
+        assert_eq!(b, a - (a - b));
+        assert_eq!(Point::new(5, 2), a + Vec2::new(1, 2) * 2);
+        assert_eq!(Vec2::new(-1, 2), -Vec2::new(1, -2));
+        assert_eq!(Vec2::ZERO, Vec2::new(1, 2) - Vec2::new(1, 2));
+
+        let mut c = Point::ORIGIN;
+        c += Vec2::new(2, 3);
+        c -= Vec2::new(1, 1);
+        assert_eq!(Point::new(1, 2), c);
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(1, 1);
+
+        assert_eq!(0, a.manhattan(a));
+        assert_eq!(7, a.manhattan(Point::new(-2, 5)));
+        assert_eq!(4, a.chebyshev(Point::new(-2, 5)));
+        assert_eq!(1, a.chebyshev(Point::new(0, 0)));
+        assert_eq!(
+            u64::MAX,
+            Point::new(i64::MIN, 0).manhattan(Point::new(i64::MAX, 0))
+        );
+    }
+
+    #[test]
+    fn grid_indices() {
+        assert_eq!(Point::new(2, 1), Point::from_index(7, 5));
+        assert_eq!(Some(7), Point::new(2, 1).to_index(5, 2));
+
+        for (x, y) in [(-1, 0), (0, -1), (5, 0), (0, 2)] {
+            assert_eq!(None, Point::new(x, y).to_index(5, 2));
+        }
+        for i in 0..10 {
+            assert_eq!(Some(i), Point::from_index(i, 5).to_index(5, 2));
+        }
+    }
+
+    #[test]
+    fn turning() {
+        for direction in Direction::ALL {
+            assert_eq!(direction, direction.turn_left().turn_right());
+            assert_eq!(
+                direction.reverse(),
+                direction.turn_right().turn_right()
+            );
+            assert_eq!(-direction.offset(), direction.reverse().offset());
+        }
+        assert_eq!(Direction::Left, Direction::Up.turn_left());
+        assert_eq!(Direction::Up, Direction::Left.turn_right());
+
+        for direction in Direction8::ALL {
+            assert_eq!(direction, direction.turn_left().turn_right());
+            assert_eq!(-direction.offset(), direction.reverse().offset());
+        }
+        assert_eq!(Direction8::UpLeft, Direction8::Up.turn_left());
+        assert_eq!(Direction8::Right, Direction::Right.into());
+    }
+
+    #[test]
+    fn steps() {
+        let a = Point::new(0, 0);
+
+        assert_eq!(Point::new(0, -1), a.step(Direction::Up));
+        assert_eq!(Point::new(-1, 1), a.step(Direction8::DownLeft));
+
+        let around = Direction8::ALL.map(|d| a.step(d));
+        assert!(around.iter().all(|&p| p.chebyshev(a) == 1));
+        assert_eq!(4, around.iter().filter(|&&p| p.manhattan(a) == 1).count());
+    }
+}
@@ -0,0 +1,261 @@
+//! Reading the examples out of a saved puzzle page, so they don't have to be
+//! copied by hand.
+//!
+//! Each part of a puzzle is an `<article class="day-desc">` on the page. Its
+//! example inputs are the `<pre><code>` blocks, and the answer the example
+//! gives is the last value shown as `<code><em>...</em></code>`.
+
+use std::path::Path;
+
+/// One part of a puzzle page.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Part {
+    /// The text of every `<pre><code>` block, in order.
+    pub examples: Vec<String>,
+    /// Every emphasised code value, in order.
+    pub emphasised: Vec<String>,
+    /// The answer to the part, once it has been solved.
+    pub solution: Option<String>,
+}
+
+impl Part {
+    /// The example's answer, which is the last value emphasised.
+    pub fn answer(&self) -> Option<&str> {
+        self.emphasised.last().map(String::as_str)
+    }
+}
+
+/// The parts of a puzzle page, which only has part 2 once part 1 is solved.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    pub parts: Vec<Part>,
+}
+
+/// An example input and the answer it is expected to give for a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub part: u8,
+    pub input: String,
+    pub expected: Option<String>,
+}
+
+impl Case {
+    /// The same example for both parts, with answers still to be filled in.
+    pub fn unanswered(input: &str) -> [Case; 2] {
+        [1, 2].map(|part| Case {
+            part,
+            input: input.to_owned(),
+            expected: None,
+        })
+    }
+}
+
+impl Puzzle {
+    pub fn parse(page: &str) -> Self {
+        let mut parts = Vec::new();
+
+        // the solution follows the part's article, before the next one
+        for section in page.split("<article").skip(1) {
+            let (article, rest) =
+                section.split_once("</article>").unwrap_or((section, ""));
+
+            parts.push(Part {
+                examples: between(article, "<pre><code>", "</code></pre>")
+                    .map(text)
+                    .collect(),
+                emphasised: between(article, "<code><em>", "</em></code>")
+                    .map(|value| text(value).trim().to_owned())
+                    .collect(),
+                solution: between(
+                    rest,
+                    "Your puzzle answer was <code>",
+                    "</code>",
+                )
+                .next()
+                .map(|value| text(value).trim().to_owned()),
+            });
+        }
+
+        Puzzle { parts }
+    }
+
+    /// Read a saved puzzle page.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let page = crate::read_file(path.to_owned())?;
+        let puzzle = Puzzle::parse(&page);
+
+        if puzzle.parts.is_empty() {
+            anyhow::bail!("`{}` is not a puzzle page", path.display());
+        }
+
+        Ok(puzzle)
+    }
+
+    /// A case for each part, using the first example of the part. A part
+    /// without an example of its own uses the one before it, as part 2
+    /// usually does.
+    pub fn cases(&self) -> Vec<Case> {
+        let mut input = None;
+
+        self.parts
+            .iter()
+            .zip(1..)
+            .filter_map(|(part, n)| {
+                if let Some(example) = part.examples.first() {
+                    input = Some(example.clone());
+                }
+
+                Some(Case {
+                    part: n,
+                    input: input.clone()?,
+                    expected: part.answer().map(str::to_owned),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Every piece of `html` between `start` and `end`.
+fn between<'a>(
+    html: &'a str,
+    start: &'a str,
+    end: &'a str,
+) -> impl Iterator<Item = &'a str> {
+    html.split(start)
+        .skip(1)
+        .filter_map(move |s| s.split_once(end).map(|(inside, _)| inside))
+}
+
+/// The text of some html, without its tags and with entities decoded.
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    decode(&text)
+}
+
+fn decode(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(i) = rest.find('&') {
+        decoded.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let c = entity.and_then(|(name, _)| match name {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => name
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| name.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        });
+
+        match (c, entity) {
+            (Some(c), Some((_, end))) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY7: &str = include_str!("../fixtures/2023-day7.html");
+    const DAY19: &str = include_str!("../fixtures/2023-day19.html");
+
+    #[test]
+    fn solved_puzzle() {
+        let puzzle = Puzzle::parse(DAY7);
+        assert_eq!(2, puzzle.parts.len());
+
+        let [part1, part2] = &puzzle.parts[..] else {
+            unreachable!()
+        };
+        assert_eq!(
+            vec!["32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n"],
+            part1.examples
+        );
+        assert_eq!(vec!["1", "6440"], part1.emphasised);
+        assert_eq!(Some("250453939"), part1.solution.as_deref());
+
+        assert!(part2.examples.is_empty());
+        assert_eq!(Some("5905"), part2.answer());
+        assert_eq!(Some("248652697"), part2.solution.as_deref());
+
+        let input = part1.examples[0].clone();
+        assert_eq!(
+            vec![
+                Case {
+                    part: 1,
+                    input: input.clone(),
+                    expected: Some(String::from("6440")),
+                },
+                Case {
+                    part: 2,
+                    input,
+                    expected: Some(String::from("5905")),
+                },
+            ],
+            puzzle.cases()
+        );
+    }
+
+    #[test]
+    fn unsolved_puzzle() {
+        let puzzle = Puzzle::parse(DAY19);
+        assert_eq!(1, puzzle.parts.len());
+
+        let part1 = &puzzle.parts[0];
+        assert!(part1.examples[0].starts_with("px{a<2006:qkq,m>2090:A,rfg}\n"));
+        assert!(part1.examples[0].ends_with("{x=2127,m=1623,a=2188,s=1013}\n"));
+        // highlighting inside an example is dropped
+        assert_eq!(
+            "{x=787,m=2655,a=1222,s=2876}: in -> qqz -> qs -> lnx -> A\n",
+            part1.examples[1]
+        );
+        assert_eq!(Some("19114"), part1.answer());
+        assert_eq!(None, part1.solution);
+
+        let cases = puzzle.cases();
+        assert_eq!(1, cases.len());
+        assert_eq!(part1.examples[0], cases[0].input);
+    }
+
+    #[test]
+    fn decode_entities() {
+        assert_eq!("a < b && c > d", decode("a &lt; b &amp;&amp; c &gt; d"));
+        assert_eq!("\"it's\"", decode("&quot;it&#39;s&#x22;"));
+        assert_eq!("AT&T; &nbsp;", decode("AT&T; &nbsp;"));
+    }
+
+    #[test]
+    fn not_a_puzzle() {
+        assert_eq!(Puzzle::default(), Puzzle::parse("<html></html>"));
+        assert!(Puzzle::default().cases().is_empty());
+    }
+}
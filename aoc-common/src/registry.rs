@@ -0,0 +1,172 @@
+//! Registry of every day solved in a year's crate.
+//!
+//! The crate's `build.rs` includes each `src/bin/dayN.rs` as a module and
+//! collects the `DAY` item that `setup!` generates for it into a [`Year`], so
+//! adding a new day file is all it takes for the runner to pick it up.
+
+use crate::{
+    bench, generate,
+    report::{self, Format},
+    solution::{Answer, Run},
+};
+
+/// A registered day, with its [`Solution`](crate::Solution) erased behind
+/// [`solve`](crate::solution::solve), [`bench`](bench::bench) and
+/// [`generate`](generate::generate).
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub name: &'static str,
+    /// Directory of the year's crate, which holds its inputs and answers.
+    pub root: &'static str,
+    pub solve: fn(&str) -> anyhow::Result<Run>,
+    pub bench: fn(&str, &bench::Options) -> anyhow::Result<bench::Report>,
+    /// Make up an input of a size from a seed, if the day can.
+    pub generate: fn(u64, usize) -> Option<String>,
+}
+
+impl Day {
+    /// Entry point of a day's binary.
+    ///
+    /// Solves both parts, or benchmarks them when `--bench` is passed. With
+    /// `--format json` the answers are printed as
+    /// [`Record`](report::Record)s instead, and with `--generated <size>` a
+    /// generated input is used instead of the real one.
+    pub fn main(&self) -> anyhow::Result<()> {
+        let args = std::env::args().skip(1).collect::<Vec<_>>();
+        let bench = bench::Options::from_args(&args)?;
+        let generated = generate::Options::from_args(&args)?;
+
+        if Format::from_args(&args)? == Format::Json {
+            if bench.is_some() {
+                anyhow::bail!("`--bench` can't be used with `--format json`");
+            }
+
+            return self.run_json(self.input(generated.as_ref()));
+        }
+
+        let input = self.input(generated.as_ref())?;
+        match bench {
+            Some(options) => self.run_bench(&input, &options),
+            None => self.run(&input),
+        }
+    }
+
+    /// The generated input if one was asked for, otherwise the real input
+    /// found by [`read_input`](crate::read_input).
+    pub fn input(
+        &self,
+        generated: Option<&generate::Options>,
+    ) -> anyhow::Result<String> {
+        match generated {
+            Some(options) => self.generated(options),
+            None => crate::read_input(self),
+        }
+    }
+
+    /// Generate an input for the day, failing if it has no generator.
+    pub fn generated(
+        &self,
+        options: &generate::Options,
+    ) -> anyhow::Result<String> {
+        match (self.generate)(options.seed, options.size) {
+            Some(input) => Ok(input),
+            None => anyhow::bail!(
+                "{} day {} has no input generator",
+                self.year,
+                self.day
+            ),
+        }
+    }
+
+    /// Solve both parts for the given input and print the answers.
+    pub fn run(&self, input: &str) -> anyhow::Result<()> {
+        let run = (self.solve)(input)?;
+
+        println!("Parsed in {:.2?}", run.parse);
+        print_answer(1, &run.part1?);
+        print_answer(2, &run.part2?);
+
+        Ok(())
+    }
+
+    /// Solve both parts for the input and print their records as JSON,
+    /// failing after printing them if either part failed.
+    pub fn run_json(
+        &self,
+        input: anyhow::Result<String>,
+    ) -> anyhow::Result<()> {
+        let records = report::solve(self, input);
+        report::print_json(&records)?;
+
+        if records.iter().any(|r| !r.success) {
+            anyhow::bail!("{} day {} failed", self.year, self.day);
+        }
+
+        Ok(())
+    }
+
+    /// Benchmark every phase for the given input and print the timings.
+    pub fn run_bench(
+        &self,
+        input: &str,
+        options: &bench::Options,
+    ) -> anyhow::Result<()> {
+        print!("{}", (self.bench)(input, options)?);
+
+        Ok(())
+    }
+}
+
+fn print_answer(part: u8, answer: &Answer) {
+    if answer.value.contains('\n') {
+        // multi-line answers start on their own line so they stay aligned
+        println!("Part {part}: ({:.2?})\n{}", answer.time, answer.value);
+    } else {
+        println!("Part {part}: {} ({:.2?})", answer.value, answer.time);
+    }
+}
+
+/// A year's crate and every day registered in it.
+pub struct Year {
+    pub year: u16,
+    /// Directory of the crate, where new days are scaffolded.
+    pub root: &'static str,
+    /// Every registered day, in order.
+    pub days: &'static [&'static Day],
+}
+
+impl Year {
+    /// Find the registered day with the given number.
+    pub fn find(&self, day: u8) -> Option<&'static Day> {
+        self.days.iter().find(|d| d.day == day).copied()
+    }
+}
+
+/// Get the number from a name, e.g. `"day7"` gives `7`.
+pub const fn number(name: &str) -> u16 {
+    let bytes = name.as_bytes();
+    let mut number = 0;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_digit() {
+            number = number * 10 + (bytes[i] - b'0') as u16;
+        }
+        i += 1;
+    }
+
+    number
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(1, number("day1"));
+        assert_eq!(25, number("day25"));
+        assert_eq!(2023, number("2023"));
+    }
+}
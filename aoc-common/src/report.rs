@@ -0,0 +1,206 @@
+//! Machine-readable records of solving a day, for `--format json`.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    registry::Day,
+    solution::{Answer, Run},
+};
+
+/// How the results of a run are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    /// An array with a [`Record`] for each part.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => anyhow::bail!("unknown format `{s}`, expected text or json"),
+        }
+    }
+}
+
+impl Format {
+    /// The format given with `--format`, which is text by default.
+    pub fn from_args(args: &[String]) -> anyhow::Result<Self> {
+        Ok(crate::flag_value(args, "--format")?
+            .map(str::parse)
+            .transpose()?
+            .unwrap_or(Format::Text))
+    }
+}
+
+/// Print records as a JSON array.
+pub fn print_json(records: &[Record]) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(records)?);
+
+    Ok(())
+}
+
+/// The outcome of solving one part of a day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// The [`normalise`](crate::solution::normalise)d text of the answer.
+    pub answer: Option<String>,
+    /// Time taken to parse the input, in nanoseconds.
+    pub parse_ns: Option<u64>,
+    /// Time taken to solve the part, in nanoseconds.
+    pub solve_ns: Option<u64>,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+impl Record {
+    fn new(day: &Day, part: u8) -> Self {
+        Record {
+            year: day.year,
+            day: day.day,
+            part,
+            answer: None,
+            parse_ns: None,
+            solve_ns: None,
+            success: false,
+            error: None,
+        }
+    }
+
+    fn failed(mut self, error: &anyhow::Error) -> Self {
+        self.error = Some(format!("{error:#}"));
+        self
+    }
+}
+
+/// Solve the day's input, giving a record for each part.
+///
+/// An input that couldn't be read or parsed fails both parts with the same
+/// error.
+pub fn solve(day: &Day, input: anyhow::Result<String>) -> [Record; 2] {
+    records(day, input.and_then(|input| (day.solve)(&input)))
+}
+
+/// A record for each part of a day from the result of solving it, which fails
+/// both parts if the day couldn't be solved at all.
+pub fn records(day: &Day, run: anyhow::Result<Run>) -> [Record; 2] {
+    let run = match run {
+        Ok(run) => run,
+        Err(e) => {
+            return [1, 2].map(|part| Record::new(day, part).failed(&e));
+        }
+    };
+
+    let record = |part: u8, answer: anyhow::Result<Answer>| {
+        let record = Record {
+            parse_ns: Some(nanos(run.parse)),
+            ..Record::new(day, part)
+        };
+
+        match answer {
+            Ok(answer) => Record {
+                answer: Some(answer.value),
+                solve_ns: Some(nanos(answer.time)),
+                success: true,
+                ..record
+            },
+            Err(e) => record.failed(&e),
+        }
+    };
+
+    [record(1, run.part1), record(2, run.part2)]
+}
+
+fn nanos(time: std::time::Duration) -> u64 {
+    time.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bench, generate, solution, Solution};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed = Vec<u32>;
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+            crate::parse_lines(input)
+        }
+
+        fn part1(numbers: &Self::Parsed) -> anyhow::Result<u32> {
+            Ok(numbers.iter().sum())
+        }
+
+        fn part2(_: &Self::Parsed) -> anyhow::Result<u32> {
+            anyhow::bail!("no part 2")
+        }
+    }
+
+    const DAY: Day = Day {
+        year: 2023,
+        day: 1,
+        name: "day1",
+        root: "",
+        solve: solution::solve::<Sum>,
+        bench: bench::bench::<Sum>,
+        generate: generate::generate::<Sum>,
+    };
+
+    #[test]
+    fn records_each_part() {
+        let [part1, part2] = solve(&DAY, Ok(String::from("1\n2")));
+
+        assert_eq!(Some("3"), part1.answer.as_deref());
+        assert!(part1.success && part1.error.is_none());
+        assert!(part1.parse_ns.is_some() && part1.solve_ns.is_some());
+
+        assert_eq!(2, part2.part);
+        assert!(!part2.success && part2.answer.is_none());
+        assert!(part2.parse_ns.is_some() && part2.solve_ns.is_none());
+        assert_eq!(
+            Some("failed to solve part 2: no part 2"),
+            part2.error.as_deref()
+        );
+    }
+
+    #[test]
+    fn bad_input_fails_both_parts() {
+        let [part1, part2] = solve(&DAY, Ok(String::from("x")));
+        assert!(part1.error.unwrap().contains("failed to parse line 1"));
+        assert!(part2.error.unwrap().contains("failed to parse line 1"));
+
+        let [part1, part2] = solve(&DAY, Err(anyhow::anyhow!("no input")));
+        assert_eq!(Some("no input"), part1.error.as_deref());
+        assert_eq!(Some("no input"), part2.error.as_deref());
+        assert!(part1.parse_ns.is_none());
+    }
+
+    #[test]
+    fn json_fields() {
+        let record = Record {
+            answer: Some(String::from("6440")),
+            parse_ns: Some(10),
+            solve_ns: Some(20),
+            success: true,
+            ..Record::new(&DAY, 1)
+        };
+
+        assert_eq!(
+            r#"{"year":2023,"day":1,"part":1,"answer":"6440","parse_ns":10,"solve_ns":20,"success":true,"error":null}"#,
+            serde_json::to_string(&record).unwrap()
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! Scaffolding for a new day, rendered from `template.rs`.
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::puzzle::Case;
+
+/// The template for a new day's binary.
+pub const TEMPLATE: &str = include_str!("../template.rs");
+
+/// The `setup!` arguments in the template, which are replaced by the
+/// examples.
+const SETUP: &str =
+    "    Example: r\"\nEXAMPLE\n    \",\n    Part1: 0,\n    Part2: 0,\n";
+
+/// An example as a raw string literal.
+fn literal(example: Option<&str>) -> String {
+    let Some(example) = example.map(str::trim) else {
+        return String::from("r\"\n    \"");
+    };
+
+    // a raw string ends at the first `"` followed by as many `#` as it
+    // started with, so it needs one more than any run in the example
+    let hashes = example
+        .split('"')
+        .skip(1)
+        .map(|after| after.len() - after.trim_start_matches('#').len() + 1)
+        .max()
+        .unwrap_or(0);
+    let hashes = "#".repeat(hashes);
+
+    format!("r{hashes}\"\n{example}\n    \"{hashes}")
+}
+
+/// An expected answer as a literal, which is `0` until it is known.
+fn answer(expected: Option<&str>) -> String {
+    match expected {
+        Some(n) if n.parse::<i64>().is_ok() => n.to_owned(),
+        Some(s) => format!("{s:?}"),
+        None => String::from("0"),
+    }
+}
+
+/// Render the template for the given year and day, embedding the example of
+/// each part that is given.
+///
+/// The parts share an example unless part 2 has a different one.
+pub fn render(year: u16, day: u8, cases: &[Case]) -> String {
+    let case = |part| cases.iter().find(|case| case.part == part);
+    let input = |part| case(part).map(|case: &Case| case.input.as_str());
+    let expected =
+        |part| answer(case(part).and_then(|c| c.expected.as_deref()));
+
+    let setup = match (input(1), input(2)) {
+        (part1, Some(part2)) if part1 != Some(part2) => format!(
+            "    Part1: {} = {},\n    Part2: {} = {},\n",
+            literal(part1),
+            expected(1),
+            literal(Some(part2)),
+            expected(2)
+        ),
+        (part1, _) => format!(
+            "    Example: {},\n    Part1: {},\n    Part2: {},\n",
+            literal(part1),
+            expected(1),
+            expected(2)
+        ),
+    };
+
+    TEMPLATE
+        .replace("YYYY", &year.to_string())
+        .replace("dayN", &format!("day{day}"))
+        .replace("DayN", &format!("Day{day}"))
+        .replace(SETUP, &setup)
+}
+
+/// List a new day's binary in the manifest of the year's crate at `root`.
+fn add_to_manifest(root: &Path, year: u16, day: u8) -> anyhow::Result<()> {
+    // the binary is named after the year to keep it unique in the workspace
+    let manifest = root.join("Cargo.toml");
+    fs::OpenOptions::new()
+        .append(true)
+        .open(&manifest)
+        .and_then(|mut file| {
+            write!(
+                file,
+                "\n[[bin]]\nname = \"{year}-day{day}\"\n\
+                 path = \"src/bin/day{day}.rs\"\n"
+            )
+        })
+        .with_context(|| {
+            format!("could not add day {day} to `{}`", manifest.display())
+        })
+}
+
+/// Create the binary for a new day inside the year's crate at `root` and list
+/// it in the crate's manifest, along with an empty input file in `input_dir` if
+/// there isn't one already. The day is tested against the given examples.
+///
+/// Refuses to overwrite a day that already exists. Gives the path of the new
+/// binary.
+pub fn new_day(
+    root: &Path,
+    input_dir: &Path,
+    year: u16,
+    day: u8,
+    cases: &[Case],
+) -> anyhow::Result<PathBuf> {
+    if !(1..=25).contains(&day) {
+        anyhow::bail!("day must be from 1 to 25, not {day}");
+    }
+
+    let path = root.join("src").join("bin").join(format!("day{day}.rs"));
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .with_context(|| {
+            format!("could not create day {day} at `{}`", path.display())
+        })?;
+
+    let written = file
+        .write_all(render(year, day, cases).as_bytes())
+        .with_context(|| format!("could not write `{}`", path.display()))
+        .and_then(|()| add_to_manifest(root, year, day));
+
+    // a day left behind would stop the next attempt from creating it
+    if let Err(e) = written {
+        let _ = fs::remove_file(&path);
+        return Err(e);
+    }
+
+    fs::create_dir_all(input_dir)?;
+    let input = input_dir.join(format!("day{day}.txt"));
+    if !input.exists() {
+        fs::write(&input, "").with_context(|| {
+            format!("could not create input `{}`", input.display())
+        })?;
+    }
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::Puzzle;
+
+    #[test]
+    fn render_names_day() {
+        let day = render(2023, 10, &[]);
+
+        assert!(day.starts_with("// Advent of Code 2023\n"));
+        assert!(day.contains("struct Day10;"));
+        assert!(day.contains("impl Solution for Day10 {"));
+        assert!(day.contains("    \"day10\",\n    Day10,\n"));
+        assert!(day.contains("    Example: r\"\n    \",\n"));
+        assert!(!day.contains("DayN") && !day.contains("dayN"));
+        assert!(!day.contains("YYYY"));
+    }
+
+    #[test]
+    fn render_embeds_example() {
+        let day =
+            render(2023, 3, &Case::unanswered("467..114..\n...*......\n"));
+        assert!(
+            day.contains("    Example: r\"\n467..114..\n...*......\n    \",\n")
+        );
+
+        let day = render(2023, 3, &Case::unanswered("say \"hi\""));
+        assert!(day.contains("    Example: r#\"\nsay \"hi\"\n    \"#,\n"));
+
+        let day = render(2023, 3, &Case::unanswered("r#\"a\"#, \"##"));
+        assert!(
+            day.contains("    Example: r###\"\nr#\"a\"#, \"##\n    \"###,\n")
+        );
+    }
+
+    #[test]
+    fn render_puzzle_answers() {
+        let cases =
+            Puzzle::parse(include_str!("../fixtures/2023-day7.html")).cases();
+        let day = render(2023, 7, &cases);
+        assert!(day.contains(
+            "    Example: r\"\n32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\n\
+             QQQJA 483\n    \",\n    Part1: 6440,\n    Part2: 5905,\n"
+        ));
+
+        let cases = [
+            Case {
+                part: 1,
+                input: String::from("1abc2"),
+                expected: Some(String::from("12")),
+            },
+            Case {
+                part: 2,
+                input: String::from("two1nine"),
+                expected: Some(String::from("CMZ")),
+            },
+        ];
+        let day = render(2023, 1, &cases);
+        assert!(day.contains(
+            "    Part1: r\"\n1abc2\n    \" = 12,\n\
+             \x20   Part2: r\"\ntwo1nine\n    \" = \"CMZ\",\n"
+        ));
+    }
+
+    #[test]
+    fn new_day_refuses_overwrite() {
+        let root = std::env::temp_dir()
+            .join(format!("aoc-scaffold-{}", std::process::id()));
+        let input_dir = root.join("input");
+        fs::create_dir_all(root.join("src").join("bin")).unwrap();
+        fs::write(root.join("Cargo.toml"), "[package]\n").unwrap();
+
+        let path =
+            new_day(&root, &input_dir, 2023, 10, &Case::unanswered("1 2 3"))
+                .unwrap();
+        assert_eq!(
+            render(2023, 10, &Case::unanswered("1 2 3")),
+            fs::read_to_string(&path).unwrap()
+        );
+        assert_eq!(
+            "",
+            fs::read_to_string(input_dir.join("day10.txt")).unwrap()
+        );
+
+        assert_eq!(
+            "[package]\n\n[[bin]]\nname = \"2023-day10\"\n\
+             path = \"src/bin/day10.rs\"\n",
+            fs::read_to_string(root.join("Cargo.toml")).unwrap()
+        );
+
+        let err = new_day(&root, &input_dir, 2023, 10, &[]).unwrap_err();
+        assert!(err.to_string().contains("could not create day 10"));
+        assert_eq!(
+            render(2023, 10, &Case::unanswered("1 2 3")),
+            fs::read_to_string(&path).unwrap()
+        );
+
+        assert!(new_day(&root, &input_dir, 2023, 26, &[]).is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn new_day_cleans_up_on_error() {
+        let root = std::env::temp_dir()
+            .join(format!("aoc-scaffold-cleanup-{}", std::process::id()));
+        let input_dir = root.join("input");
+        fs::create_dir_all(root.join("src").join("bin")).unwrap();
+
+        // there is no manifest to add the day to
+        let err = new_day(&root, &input_dir, 2023, 10, &[]).unwrap_err();
+        assert!(err.to_string().contains("could not add day 10"));
+        assert!(!root.join("src").join("bin").join("day10.rs").exists());
+
+        fs::write(root.join("Cargo.toml"), "[package]\n").unwrap();
+        assert!(new_day(&root, &input_dir, 2023, 10, &[]).is_ok());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,240 @@
+//! The [`Solution`] trait implemented by every day.
+
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+
+use crate::generate::Rng;
+
+/// A day's solution, split into parsing the input and solving each part.
+///
+/// The input is only parsed once, and both parts share the parsed value. Any
+/// phase can fail, and should add context to its errors to say why.
+///
+/// Answers can be of any type that can be displayed, such as numbers,
+/// strings, or multi-line ASCII art. They are compared by their [`normalise`]d
+/// text.
+pub trait Solution {
+    /// The input after it has been parsed.
+    type Parsed;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Answer1>;
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Answer2>;
+
+    /// Other implementations of part 1, such as a brute force kept to check a
+    /// faster one against. The [`differential`](crate::differential) tests
+    /// check that they all agree with [`Solution::part1`].
+    fn part1_alternatives() -> Vec<Implementation<Self::Parsed, Self::Answer1>>
+    {
+        Vec::new()
+    }
+
+    /// Other implementations of part 2, like [`Solution::part1_alternatives`].
+    fn part2_alternatives() -> Vec<Implementation<Self::Parsed, Self::Answer2>>
+    {
+        Vec::new()
+    }
+
+    /// A valid input of the given size made up from `rng`, for benchmarking
+    /// how the solution scales. Days without a generator give `None`.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+}
+
+/// An implementation of a part, named so a disagreement can say which.
+pub type Implementation<P, A> = (&'static str, fn(&P) -> anyhow::Result<A>);
+
+/// The answer to a part and how long it took to solve.
+#[derive(Debug, Clone)]
+pub struct Answer {
+    /// The [`normalise`]d text of the answer.
+    pub value: String,
+    pub time: Duration,
+}
+
+/// The outcome of solving a day: how long parsing took and the answer to
+/// each part, or why it couldn't be solved.
+#[derive(Debug)]
+pub struct Run {
+    pub parse: Duration,
+    pub part1: anyhow::Result<Answer>,
+    pub part2: anyhow::Result<Answer>,
+}
+
+fn timed<T: Display>(
+    f: impl FnOnce() -> anyhow::Result<T>,
+) -> anyhow::Result<Answer> {
+    let start = Instant::now();
+    let value = f()?;
+    let time = start.elapsed();
+
+    Ok(Answer {
+        value: normalise(&value.to_string()),
+        time,
+    })
+}
+
+/// Normalise the text of an answer so that answers compare equal regardless
+/// of how they were written down.
+///
+/// Trailing whitespace is removed from every line, along with any blank lines
+/// before or after the answer. This lets multi-line answers be written as raw
+/// string literals that start on a new line.
+pub fn normalise(answer: &str) -> String {
+    let lines = answer.lines().map(str::trim_end).collect::<Vec<_>>();
+
+    let start = lines.iter().position(|l| !l.is_empty());
+    let end = lines.iter().rposition(|l| !l.is_empty());
+
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Trim an example down to the input it holds.
+///
+/// Examples are written as raw string literals that start on a new line and
+/// end on an indented line, so the line break after the opening quote and the
+/// whitespace before the closing quote are removed. Leading whitespace on the
+/// first line is kept, since it can be part of the input (e.g. a drawing).
+pub fn example(input: &str) -> &str {
+    input.trim_start_matches(['\r', '\n']).trim_end()
+}
+
+/// Assert that a part gave the expected answer, comparing their normalised
+/// text. Used by the tests that `setup!` generates.
+#[track_caller]
+pub fn assert_answer<T: Display>(
+    expected: impl Display,
+    answer: anyhow::Result<T>,
+) {
+    let answer = answer
+        .map(|a| normalise(&a.to_string()))
+        .map_err(|e| format!("{e:#}"));
+
+    assert_eq!(Ok(normalise(&expected.to_string())), answer);
+}
+
+/// What an example is expected to give: either an answer, which can be any
+/// displayable value, or an error when the example is expected to [`Fails`].
+pub trait Expected {
+    fn assert<T: Display>(&self, answer: anyhow::Result<T>);
+}
+
+impl<E: Display> Expected for E {
+    #[track_caller]
+    fn assert<T: Display>(&self, answer: anyhow::Result<T>) {
+        assert_answer(self, answer);
+    }
+}
+
+/// An example that is expected to fail with an error containing the message.
+#[derive(Debug, Clone, Copy)]
+pub struct Fails(pub &'static str);
+
+impl Expected for Fails {
+    #[track_caller]
+    fn assert<T: Display>(&self, answer: anyhow::Result<T>) {
+        match answer {
+            Ok(answer) => panic!(
+                "expected an error containing `{}`, got the answer `{answer}`",
+                self.0
+            ),
+            Err(e) => {
+                let e = format!("{e:#}");
+                assert!(
+                    e.contains(self.0),
+                    "expected an error containing `{}`, got `{e}`",
+                    self.0
+                );
+            }
+        }
+    }
+}
+
+/// Parse the input once then solve both parts, timing each phase.
+///
+/// Only fails if the input can't be parsed, since each part is solved even if
+/// the other part fails.
+pub fn solve<S: Solution>(input: &str) -> anyhow::Result<Run> {
+    let start = Instant::now();
+    let parsed = S::parse(input).context("failed to parse the input")?;
+    let parse = start.elapsed();
+
+    Ok(Run {
+        parse,
+        part1: timed(|| S::part1(&parsed)).context("failed to solve part 1"),
+        part2: timed(|| S::part2(&parsed)).context("failed to solve part 2"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalise_answers() {
+        assert_eq!("6440", normalise("6440"));
+        assert_eq!("CMZ", normalise("\nCMZ  \n    "));
+        assert_eq!("", normalise(" \n \n"));
+
+        let art = r"
+#..#
+ ##
+    ";
+        assert_eq!("#..#\n ##", normalise(art));
+    }
+
+    #[test]
+    fn trim_examples() {
+        assert_eq!("A Y\nB X", example("\nA Y\nB X\n    "));
+        assert_eq!("    [D]\n[N] [C]", example("\n    [D]\n[N] [C]\n    "));
+        assert_eq!("abcdef", example("abcdef"));
+    }
+
+    #[test]
+    fn assert_answer_kinds() {
+        assert_answer(6440, Ok(6440u64));
+        assert_answer("6440", Ok(6440u64));
+        assert_answer("CMZ", Ok(String::from("CMZ")));
+        assert_answer("\n#.\n.#\n    ", Ok("#.\n.#"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn assert_answer_wrong() {
+        assert_answer("CMZ", Ok("MCD"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn assert_answer_error() {
+        assert_answer::<u64>(1, Err(anyhow::anyhow!("failed")));
+    }
+
+    #[test]
+    fn expected_fails() {
+        let error = anyhow::anyhow!("unknown node").context("part 1");
+        Fails("unknown node").assert::<u64>(Err(error));
+    }
+
+    #[test]
+    #[should_panic]
+    fn expected_fails_with_answer() {
+        Fails("unknown node").assert(Ok(6));
+    }
+
+    #[test]
+    #[should_panic]
+    fn expected_fails_with_other_error() {
+        Fails("unknown node").assert::<u64>(Err(anyhow::anyhow!("bad input")));
+    }
+}
@@ -0,0 +1,53 @@
+//! Plain text tables for the terminal.
+
+use std::fmt;
+
+/// Which side of its column a cell is lined up against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Align {
+    Left,
+    Right,
+}
+
+/// Write the rows under the header, with every column as wide as its widest
+/// cell.
+pub(crate) fn write<const N: usize>(
+    f: &mut fmt::Formatter<'_>,
+    header: [&str; N],
+    align: [Align; N],
+    rows: &[[String; N]],
+) -> fmt::Result {
+    let widths: [usize; N] = std::array::from_fn(|i| {
+        rows.iter()
+            .map(|row| row[i].len())
+            .chain([header[i].len()])
+            .max()
+            .unwrap_or_default()
+    });
+
+    for row in [header.map(String::from)].iter().chain(rows) {
+        let mut line = String::new();
+
+        for (i, cell) in row.iter().enumerate() {
+            if i > 0 {
+                line.push_str("  ");
+            }
+
+            let pad = " ".repeat(widths[i] - cell.len());
+            match align[i] {
+                Align::Left => {
+                    line.push_str(cell);
+                    line.push_str(&pad);
+                }
+                Align::Right => {
+                    line.push_str(&pad);
+                    line.push_str(cell);
+                }
+            }
+        }
+
+        writeln!(f, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
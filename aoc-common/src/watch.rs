@@ -0,0 +1,316 @@
+//! Watching a day's source and input, to rebuild it and run its examples and
+//! real input again whenever either changes.
+//!
+//! Changes are found by polling the files, so nothing platform specific is
+//! needed.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Context;
+
+use crate::{registry::Day, report::Record};
+
+/// How often the files are polled, unless told otherwise.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification time and length of a file, or `None` if it is missing.
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Files that are polled for changes.
+pub struct Poller {
+    files: Vec<(PathBuf, Option<(SystemTime, u64)>)>,
+}
+
+impl Poller {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Poller {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let stamp = stamp(&path);
+                    (path, stamp)
+                })
+                .collect(),
+        }
+    }
+
+    /// The files that have changed since they were last polled, including
+    /// any that were created or removed.
+    pub fn poll(&mut self) -> Vec<&Path> {
+        self.files
+            .iter_mut()
+            .filter_map(|(path, last)| {
+                let stamp = stamp(path);
+                if stamp == *last {
+                    return None;
+                }
+
+                *last = stamp;
+                Some(path.as_path())
+            })
+            .collect()
+    }
+
+    /// Wait until a file changes, giving the paths of every file that did.
+    ///
+    /// Editors often write a file more than once when saving it, so the
+    /// files are left to settle for another interval first.
+    pub fn wait(&mut self, interval: Duration) -> Vec<PathBuf> {
+        loop {
+            thread::sleep(interval);
+
+            let mut changed = self
+                .poll()
+                .into_iter()
+                .map(Path::to_owned)
+                .collect::<Vec<_>>();
+            if changed.is_empty() {
+                continue;
+            }
+
+            thread::sleep(interval);
+            for path in self.poll() {
+                if !changed.iter().any(|p| p == path) {
+                    changed.push(path.to_owned());
+                }
+            }
+
+            return changed;
+        }
+    }
+}
+
+/// How each part's answer changed from the last run, a line for each part.
+pub fn diff(last: &[Record], records: &[Record]) -> String {
+    let mut diff = String::new();
+
+    for record in records {
+        let before = last.iter().find(|r| r.part == record.part);
+        let before = before.and_then(|r| r.answer.as_ref());
+        let error = record.error.as_deref().unwrap_or("no answer");
+
+        let change = match (before, &record.answer) {
+            (Some(before), Some(answer)) if before == answer => {
+                format!("{answer} (unchanged)")
+            }
+            (Some(before), Some(answer)) => format!("{before} -> {answer}"),
+            (None, Some(answer)) => answer.clone(),
+            (Some(before), None) => format!("{before} -> failed: {error}"),
+            (None, None) => format!("failed: {error}"),
+        };
+
+        diff.push_str(&format!("part {}: {change}\n", record.part));
+    }
+
+    diff
+}
+
+/// Run cargo, capturing its output.
+fn cargo(args: &[&str]) -> anyhow::Result<Output> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+
+    Command::new(cargo)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .context("failed to run cargo")
+}
+
+/// Run the day's example tests, giving a summary of them and whether the day
+/// built.
+fn examples(manifest: &str, bin: &str) -> anyhow::Result<(String, bool)> {
+    let output = cargo(&[
+        "test",
+        "--quiet",
+        "--manifest-path",
+        manifest,
+        "--bin",
+        bin,
+        "--",
+        "part1",
+        "part2",
+    ])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    if output.status.success() {
+        let passed = stdout
+            .lines()
+            .filter_map(|line| line.strip_prefix("test result: ok. "))
+            .filter_map(|line| line.split(' ').next()?.parse::<usize>().ok())
+            .sum::<usize>();
+        return Ok((format!("examples: {passed} passed\n"), true));
+    }
+
+    // without any results the day didn't build
+    let (summary, built) = match stdout.split_once("\nfailures:\n") {
+        Some((_, failures)) => (
+            failures
+                .split_once("\ntest result:")
+                .map_or(failures, |(failures, _)| failures)
+                .trim()
+                .to_owned(),
+            true,
+        ),
+        None => (
+            String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            false,
+        ),
+    };
+
+    Ok((format!("examples: failed\n{summary}\n"), built))
+}
+
+/// Solve the day's real input with its binary.
+fn solve(
+    manifest: &str,
+    bin: &str,
+    input: &Path,
+) -> anyhow::Result<Vec<Record>> {
+    let input = input.to_string_lossy();
+    let output = cargo(&[
+        "run",
+        "--quiet",
+        "--manifest-path",
+        manifest,
+        "--bin",
+        bin,
+        "--",
+        "--format",
+        "json",
+        "--input",
+        &input,
+    ])?;
+
+    // a day that fails still prints its records before its error
+    match serde_json::from_slice(&output.stdout) {
+        Ok(records) => Ok(records),
+        Err(_) => {
+            anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim())
+        }
+    }
+}
+
+/// Watch the day's source and input, running its examples and real input
+/// each time one changes until the process is stopped.
+pub fn watch(day: &Day, interval: Duration) -> anyhow::Result<()> {
+    let root = Path::new(day.root);
+    let manifest = root.join("Cargo.toml");
+    let manifest = manifest.to_string_lossy();
+    let bin = format!("{}-{}", day.year, day.name);
+    let source = root
+        .join("src")
+        .join("bin")
+        .join(format!("{}.rs", day.name));
+    let input = crate::input_path(day);
+
+    let mut poller = Poller::new(vec![source.clone(), input.clone()]);
+    let mut last = Vec::new();
+
+    println!(
+        "watching {} and {} (ctrl-c to stop)",
+        source.display(),
+        input.display()
+    );
+
+    loop {
+        println!("\n{} day {}", day.year, day.day);
+
+        let (summary, built) = examples(&manifest, &bin)?;
+        print!("{summary}");
+
+        if built {
+            match solve(&manifest, &bin, &input) {
+                Ok(records) => {
+                    print!("{}", diff(&last, &records));
+                    last = records;
+                }
+                Err(e) => println!("failed: {e:#}"),
+            }
+        }
+
+        for path in poller.wait(interval) {
+            println!("\nchanged: {}", path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(part: u8, answer: Option<&str>, error: Option<&str>) -> Record {
+        Record {
+            year: 2023,
+            day: 7,
+            part,
+            answer: answer.map(String::from),
+            parse_ns: None,
+            solve_ns: None,
+            success: answer.is_some(),
+            error: error.map(String::from),
+        }
+    }
+
+    #[test]
+    fn diff_answers() {
+        let first = [
+            record(1, Some("6440"), None),
+            record(2, None, Some("not yet")),
+        ];
+        assert_eq!(
+            "part 1: 6440\npart 2: failed: not yet\n",
+            diff(&[], &first)
+        );
+
+        let second =
+            [record(1, Some("6440"), None), record(2, Some("5905"), None)];
+        assert_eq!(
+            "part 1: 6440 (unchanged)\npart 2: 5905\n",
+            diff(&first, &second)
+        );
+
+        let third = [
+            record(1, Some("6592"), None),
+            record(2, None, Some("bad hand")),
+        ];
+        assert_eq!(
+            "part 1: 6440 -> 6592\npart 2: 5905 -> failed: bad hand\n",
+            diff(&second, &third)
+        );
+    }
+
+    #[test]
+    fn poll_changes() {
+        let dir = std::env::temp_dir()
+            .join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("day7.rs");
+        let input = dir.join("day7.txt");
+        fs::write(&source, "fn main() {}").unwrap();
+
+        let mut poller = Poller::new(vec![source.clone(), input.clone()]);
+        assert!(poller.poll().is_empty());
+
+        // the length changes even if the time is too coarse to
+        fs::write(&source, "fn main() { todo!() }").unwrap();
+        assert_eq!(vec![source.as_path()], poller.poll());
+        assert!(poller.poll().is_empty());
+
+        fs::write(&input, "32T3K 765").unwrap();
+        assert_eq!(vec![input.as_path()], poller.poll());
+
+        fs::remove_file(&input).unwrap();
+        assert_eq!(vec![input.clone()], poller.wait(Duration::from_millis(10)));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
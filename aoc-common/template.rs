@@ -0,0 +1,34 @@
+// Advent of Code YYYY
+// Liam Fenneman
+
+use aoc_common::Solution;
+
+struct DayN;
+
+impl Solution for DayN {
+    type Parsed = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(input.lines().map(str::to_owned).collect())
+    }
+
+    fn part1(_lines: &Self::Parsed) -> anyhow::Result<u64> {
+        todo!()
+    }
+
+    fn part2(_lines: &Self::Parsed) -> anyhow::Result<u64> {
+        todo!()
+    }
+}
+
+aoc_common::setup! {
+    "dayN",
+    DayN,
+    Example: r"
+EXAMPLE
+    ",
+    Part1: 0,
+    Part2: 0,
+}
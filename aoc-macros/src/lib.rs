@@ -0,0 +1,771 @@
+//! Attribute macros for writing a day as plain functions, as an alternative to
+//! `aoc_common::setup!`. They are re-exported by `aoc_common`.
+//!
+//! ```ignore
+//! #[aoc_common::aoc(day = 7, part = 1)]
+//! fn part1(hands: &[Hand]) -> u64 {
+//!     ...
+//! }
+//!
+//! #[aoc_common::aoc(day = 7, parse)]
+//! fn hands(input: &str) -> anyhow::Result<Vec<Hand>> {
+//!     ...
+//! }
+//!
+//! #[aoc_common::aoc(day = 7, part = 2)]
+//! fn part2(hands: &[Hand]) -> anyhow::Result<u64> {
+//!     ...
+//! }
+//!
+//! #[aoc_common::aoc_example(part = 1, expected = 6440)]
+//! #[aoc_common::aoc_example(part = 2, expected = 5905)]
+//! const EXAMPLE: &str = r"
+//! 32T3K 765
+//! ...
+//!     ";
+//! ```
+//!
+//! The input is parsed once by the `parse` function, and the parts take a
+//! reference to what it gives. Without one, the parts take the input as
+//! `&str` instead.
+//!
+//! Part 2 registers the day with the runner, so it must come after part 1.
+//! It can also name the day's input generator, as in
+//! `#[aoc(day = 7, part = 2, generator = generate)]`, which is a
+//! `fn(&mut aoc_common::generate::Rng, usize) -> String`. Each example
+//! becomes a test named after its part and constant, such as
+//! `part1_example`.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse::Parser, punctuated::Punctuated, spanned::Spanned, Expr, ExprLit,
+    FnArg, GenericArgument, Ident, Item, ItemFn, Lit, Meta, PathArguments,
+    ReturnType, Token, Type,
+};
+
+/// Mark a function as solving a part of a day, `#[aoc(day = 7, part = 1)]`,
+/// or as parsing its input, `#[aoc(day = 7, parse)]`.
+///
+/// The parser takes the input as `&str`, and a part takes a reference to what
+/// the parser gives, or the input itself if the day has no parser. Each
+/// returns its value or an `anyhow::Result` of it. Part 2 can also be given a
+/// `generator`.
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    expand_aoc(args.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Test a day against an example constant:
+/// `#[aoc_example(part = 1, expected = 6440)]`.
+///
+/// `expected` can be anything that `setup!` accepts as an expected answer,
+/// such as `aoc_common::Fails("...")`.
+#[proc_macro_attribute]
+pub fn aoc_example(args: TokenStream, item: TokenStream) -> TokenStream {
+    expand_example(args.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The `key = value` and bare `key` arguments of an attribute.
+struct Args {
+    /// The attribute itself, for errors about the arguments as a whole.
+    span: Span,
+    example: &'static str,
+    values: Vec<(Ident, Option<Expr>)>,
+}
+
+impl Args {
+    fn parse(
+        args: TokenStream2,
+        known: &[&str],
+        example: &'static str,
+    ) -> syn::Result<Self> {
+        let parsed = Punctuated::<Meta, Token![,]>::parse_terminated
+            .parse2(args)
+            .map_err(|e| {
+                syn::Error::new(e.span(), format!("{e}, e.g. `{example}`"))
+            })?;
+
+        let mut values = Vec::<(Ident, Option<Expr>)>::new();
+        for arg in parsed {
+            let (path, value) = match arg {
+                Meta::Path(path) => (path, None),
+                Meta::NameValue(arg) => (arg.path, Some(arg.value)),
+                Meta::List(list) => {
+                    return Err(syn::Error::new_spanned(
+                        list,
+                        format!("expected `key = value`, e.g. `{example}`"),
+                    ))
+                }
+            };
+            let Some(key) = path.get_ident() else {
+                return Err(unknown(&path, known));
+            };
+            if !known.iter().any(|k| key == k) {
+                return Err(unknown(key, known));
+            }
+            if values.iter().any(|(k, _)| k == key) {
+                return Err(syn::Error::new_spanned(
+                    key,
+                    format!("`{key}` is given more than once"),
+                ));
+            }
+
+            values.push((key.clone(), value));
+        }
+
+        Ok(Args {
+            span: Span::call_site(),
+            example,
+            values,
+        })
+    }
+
+    fn find(&self, key: &str) -> Option<&(Ident, Option<Expr>)> {
+        self.values.iter().find(|(k, _)| k == key)
+    }
+
+    fn get(&self, key: &str) -> syn::Result<&Expr> {
+        match self.find(key) {
+            Some((_, Some(value))) => Ok(value),
+            Some((key, None)) => Err(syn::Error::new_spanned(
+                key,
+                format!("`{key}` needs a value, e.g. `{}`", self.example),
+            )),
+            None => Err(syn::Error::new(
+                self.span,
+                format!("missing `{key}`, e.g. `{}`", self.example),
+            )),
+        }
+    }
+
+    /// Whether a bare `key` argument, which takes no value, is given.
+    fn flag(&self, key: &str) -> syn::Result<bool> {
+        match self.find(key) {
+            Some((_, Some(value))) => Err(syn::Error::new_spanned(
+                value,
+                format!("`{key}` doesn't take a value"),
+            )),
+            Some(_) => Ok(true),
+            None => Ok(false),
+        }
+    }
+
+    /// An integer argument, which must be in the range.
+    fn int(&self, key: &str, min: u8, max: u8) -> syn::Result<u8> {
+        let value = self.get(key)?;
+        let error = || {
+            syn::Error::new_spanned(
+                value,
+                format!("`{key}` must be a number from {min} to {max}"),
+            )
+        };
+
+        match value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(int), ..
+            }) => int
+                .base10_parse::<u8>()
+                .ok()
+                .filter(|n| (min..=max).contains(n))
+                .ok_or_else(error),
+            _ => Err(error()),
+        }
+    }
+}
+
+fn unknown(key: impl quote::ToTokens, known: &[&str]) -> syn::Error {
+    let mut known = known.iter().map(|k| format!("`{k}`")).collect::<Vec<_>>();
+    let last = known.pop().unwrap_or_default();
+    let known = if known.is_empty() {
+        last
+    } else {
+        format!("{} or {last}", known.join(", "))
+    };
+
+    syn::Error::new_spanned(key, format!("unknown argument, expected {known}"))
+}
+
+/// Whether the type is `&str`.
+fn is_str_ref(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) => match reference.elem.as_ref() {
+            Type::Path(path) => path.path.is_ident("str"),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// The type a function gives, and whether it is returned in a `Result`.
+/// `missing` is the error for a function that doesn't return anything.
+fn answer_type(function: &ItemFn, missing: &str) -> syn::Result<(Type, bool)> {
+    let ReturnType::Type(_, ty) = &function.sig.output else {
+        return Err(syn::Error::new_spanned(&function.sig, missing));
+    };
+
+    if let Type::Path(path) = ty.as_ref() {
+        let last = path.path.segments.last();
+        if let Some(segment) = last.filter(|s| s.ident == "Result") {
+            if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                if let Some(GenericArgument::Type(answer)) = args.args.first() {
+                    return Ok((answer.clone(), true));
+                }
+            }
+        }
+    }
+
+    Ok((ty.as_ref().clone(), false))
+}
+
+/// The type of the function's only argument, or `message` as an error.
+fn argument_type<'a>(
+    function: &'a ItemFn,
+    message: &str,
+) -> syn::Result<&'a Type> {
+    let inputs = &function.sig.inputs;
+    match inputs.first() {
+        Some(FnArg::Typed(input)) if inputs.len() == 1 => Ok(&input.ty),
+        _ if inputs.is_empty() => {
+            Err(syn::Error::new_spanned(&function.sig, message))
+        }
+        _ => Err(syn::Error::new_spanned(inputs, message)),
+    }
+}
+
+/// A call of the function with `input`, as an `anyhow::Result`.
+fn call(function: &ItemFn, is_result: bool) -> TokenStream2 {
+    let name = &function.sig.ident;
+    if is_result {
+        quote!(::core::result::Result::map_err(#name(input), ::core::convert::Into::into))
+    } else {
+        quote!(::core::result::Result::Ok(#name(input)))
+    }
+}
+
+fn expand_aoc(
+    args: TokenStream2,
+    item: TokenStream2,
+) -> syn::Result<TokenStream2> {
+    let args = Args::parse(
+        args,
+        &["day", "part", "parse", "generator"],
+        "#[aoc(day = 7, part = 1)]",
+    )?;
+    let day = args.int("day", 1, 25)?;
+
+    let Item::Fn(function) = syn::parse2::<Item>(item.clone())? else {
+        return Err(syn::Error::new_spanned(
+            item,
+            "`#[aoc]` can only be used on a function",
+        ));
+    };
+
+    if args.flag("parse")? {
+        for key in ["part", "generator"] {
+            if let Some((key, _)) = args.find(key) {
+                return Err(syn::Error::new_spanned(
+                    key,
+                    format!("`{key}` can't be given with `parse`"),
+                ));
+            }
+        }
+
+        return expand_parse(&args, day, function);
+    }
+
+    let part = args.int("part", 1, 2)?;
+    let generator = match args.find("generator") {
+        Some(_) => Some(args.get("generator")?),
+        None => None,
+    };
+
+    let input = argument_type(
+        &function,
+        "a part must take its input as `&str`, or a reference to what the \
+         day's `parse` function gives",
+    )?;
+    if !matches!(input, Type::Reference(_)) {
+        return Err(syn::Error::new_spanned(
+            input,
+            "a part must take a reference to its input",
+        ));
+    }
+    let takes_str = is_str_ref(input);
+
+    let (answer, is_result) =
+        answer_type(&function, "a part must return its answer")?;
+    let call = call(&function, is_result);
+
+    let answer_alias = format_ident!("__AocAnswer{part}");
+    let wrapper = format_ident!("__aoc_part{part}");
+    let mut expanded = quote! {
+        #function
+
+        #[doc(hidden)]
+        type #answer_alias = #answer;
+
+        #[doc(hidden)]
+        fn #wrapper(input: &__AocInput) -> ::anyhow::Result<#answer_alias> {
+            #call
+        }
+    };
+
+    if part == 1 {
+        if let Some(generator) = generator {
+            return Err(syn::Error::new_spanned(
+                generator,
+                "`generator` is given with part 2, which registers the day",
+            ));
+        }
+
+        expanded.extend(quote! {
+            #[doc(hidden)]
+            const __AOC_DAY: u8 = #day;
+        });
+        return Ok(expanded);
+    }
+
+    // part 1 has to have been given already, and for the same day
+    let day_value = args.get("day")?;
+    let name = format!("day{day}");
+    let generate = generator.map(|generator| {
+        quote! {
+            fn generate(
+                rng: &mut ::aoc_common::generate::Rng,
+                size: ::core::primitive::usize,
+            ) -> ::core::option::Option<::std::string::String> {
+                ::core::option::Option::Some(#generator(rng, size))
+            }
+        }
+    });
+    expanded.extend(quote_spanned! {day_value.span()=>
+        const _: () = ::core::assert!(
+            __AOC_DAY == #day,
+            "part 1 and part 2 are for different days",
+        );
+    });
+
+    // the parts take the input itself when the day has no parser
+    let (input, parse) = if takes_str {
+        (
+            quote!(::std::string::String),
+            quote!(::core::result::Result::Ok(
+                ::std::borrow::ToOwned::to_owned(input)
+            )),
+        )
+    } else {
+        (quote!(__AocParsed), quote!(__aoc_parse(input)))
+    };
+    expanded.extend(quote! {
+        #[doc(hidden)]
+        type __AocInput = #input;
+
+        #[doc(hidden)]
+        struct __AocSolution;
+
+        impl ::aoc_common::Solution for __AocSolution {
+            type Parsed = __AocInput;
+            type Answer1 = __AocAnswer1;
+            type Answer2 = __AocAnswer2;
+
+            fn parse(input: &::core::primitive::str) -> ::anyhow::Result<Self::Parsed> {
+                #parse
+            }
+
+            fn part1(input: &Self::Parsed) -> ::anyhow::Result<__AocAnswer1> {
+                __aoc_part1(input)
+            }
+
+            fn part2(input: &Self::Parsed) -> ::anyhow::Result<__AocAnswer2> {
+                __aoc_part2(input)
+            }
+
+            #generate
+        }
+
+        pub const DAY: ::aoc_common::registry::Day = ::aoc_common::registry::Day {
+            year: ::aoc_common::registry::number(::core::env!(
+                "AOC_YEAR",
+                "the crate's build.rs must call `aoc_common::build::register_days`"
+            )),
+            day: #day,
+            name: #name,
+            root: ::core::env!("CARGO_MANIFEST_DIR"),
+            solve: ::aoc_common::solution::solve::<__AocSolution>,
+            bench: ::aoc_common::bench::bench::<__AocSolution>,
+            generate: ::aoc_common::generate::generate::<__AocSolution>,
+        };
+
+        // unused when the day is included by the registry
+        #[allow(dead_code)]
+        fn main() -> ::anyhow::Result<()> {
+            DAY.main()
+        }
+
+        #[test]
+        fn real_input() {
+            ::aoc_common::answers::test_real_input(&DAY);
+        }
+
+        #[test]
+        fn generated_input() {
+            ::aoc_common::generate::test_generated(&DAY);
+        }
+    });
+
+    Ok(expanded)
+}
+
+fn expand_parse(
+    args: &Args,
+    day: u8,
+    function: ItemFn,
+) -> syn::Result<TokenStream2> {
+    let input =
+        argument_type(&function, "a parser must take its input as `&str`")?;
+    if !is_str_ref(input) {
+        return Err(syn::Error::new_spanned(
+            input,
+            "a parser must take its input as `&str`",
+        ));
+    }
+
+    let (parsed, is_result) =
+        answer_type(&function, "a parser must return what it parses")?;
+    let call = call(&function, is_result);
+    let day_value = args.get("day")?;
+
+    let mut expanded = quote! {
+        #function
+
+        #[doc(hidden)]
+        type __AocParsed = #parsed;
+
+        #[doc(hidden)]
+        fn __aoc_parse(input: &::core::primitive::str) -> ::anyhow::Result<__AocParsed> {
+            #call
+        }
+    };
+    expanded.extend(quote_spanned! {day_value.span()=>
+        const _: () = ::core::assert!(
+            __AOC_DAY == #day,
+            "the parser and the parts are for different days",
+        );
+    });
+
+    Ok(expanded)
+}
+
+fn expand_example(
+    args: TokenStream2,
+    item: TokenStream2,
+) -> syn::Result<TokenStream2> {
+    let args = Args::parse(
+        args,
+        &["part", "expected"],
+        "#[aoc_example(part = 1, expected = 6440)]",
+    )?;
+    let part = args.int("part", 1, 2)?;
+    let expected = args.get("expected")?;
+
+    let example =
+        match syn::parse2::<Item>(item.clone())? {
+            Item::Const(item) => item.ident,
+            Item::Static(item) => item.ident,
+            _ => return Err(syn::Error::new_spanned(
+                item,
+                "`#[aoc_example]` can only be used on a `const` or `static`",
+            )),
+        };
+
+    let test = format_ident!(
+        "part{part}_{}",
+        example.to_string().to_lowercase(),
+        span = example.span()
+    );
+    let solve = format_ident!("part{part}");
+
+    Ok(quote! {
+        // examples are only used by their tests
+        #[cfg_attr(not(test), allow(dead_code))]
+        #item
+
+        #[cfg(test)]
+        #[test]
+        fn #test() {
+            use ::aoc_common::{solution::Expected, Solution};
+
+            let answer = <__AocSolution as Solution>::parse(
+                ::aoc_common::solution::example(#example),
+            )
+            .and_then(|parsed| <__AocSolution as Solution>::#solve(&parsed));
+            (#expected).assert(answer);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aoc_error(args: TokenStream2, item: TokenStream2) -> String {
+        expand_aoc(args, item).unwrap_err().to_string()
+    }
+
+    fn example_error(args: TokenStream2, item: TokenStream2) -> String {
+        expand_example(args, item).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn expands_parts() {
+        let part1 = expand_aoc(
+            quote!(day = 7, part = 1),
+            quote!(
+                fn part1(input: &str) -> anyhow::Result<u64> {
+                    todo!()
+                }
+            ),
+        )
+        .unwrap()
+        .to_string();
+        assert!(part1.contains("type __AocAnswer1 = u64"));
+        assert!(part1.contains("const __AOC_DAY : u8 = 7u8"));
+        assert!(!part1.contains("pub const DAY"));
+
+        let part2 = expand_aoc(
+            quote!(day = 7, part = 2),
+            quote!(
+                fn part2(input: &str) -> String {
+                    todo!()
+                }
+            ),
+        )
+        .unwrap()
+        .to_string();
+        assert!(part2.contains("type __AocAnswer2 = String"));
+        assert!(
+            part2.contains(":: core :: result :: Result :: Ok (part2 (input))")
+        );
+        assert!(part2.contains("pub const DAY"));
+        assert!(part2.contains("name : \"day7\""));
+        assert!(part2.contains("fn real_input"));
+        assert!(!part2.contains("fn generate ("));
+
+        let generated = expand_aoc(
+            quote!(day = 7, part = 2, generator = hands),
+            quote!(
+                fn part2(input: &str) -> u64 {
+                    todo!()
+                }
+            ),
+        )
+        .unwrap()
+        .to_string();
+        assert!(generated.contains("fn generate ("));
+        assert!(generated.contains("Some (hands (rng , size))"));
+    }
+
+    #[test]
+    fn expands_parser() {
+        let parse = expand_aoc(
+            quote!(day = 7, parse),
+            quote!(
+                fn hands(input: &str) -> anyhow::Result<Vec<Hand>> {
+                    todo!()
+                }
+            ),
+        )
+        .unwrap()
+        .to_string();
+        assert!(parse.contains("type __AocParsed = Vec < Hand >"));
+        assert!(parse.contains("map_err (hands (input)"));
+        assert!(parse.contains("__AOC_DAY == 7u8"));
+
+        // a part that takes what was parsed uses the parser, and one that
+        // takes the input keeps it as it is
+        let part2 = |input| {
+            expand_aoc(
+                quote!(day = 7, part = 2),
+                quote!(
+                    fn part2(input: #input) -> u64 {
+                        todo!()
+                    }
+                ),
+            )
+            .unwrap()
+            .to_string()
+        };
+
+        let parsed = part2(quote!(&[Hand]));
+        assert!(parsed.contains("type __AocInput = __AocParsed"));
+        assert!(parsed.contains("__aoc_parse (input)"));
+        assert!(parsed.contains("fn __aoc_part2 (input : & __AocInput)"));
+
+        let unparsed = part2(quote!(&str));
+        assert!(
+            unparsed.contains("type __AocInput = :: std :: string :: String")
+        );
+        assert!(!unparsed.contains("__aoc_parse"));
+    }
+
+    #[test]
+    fn expands_examples() {
+        let example = expand_example(
+            quote!(part = 2, expected = aoc_common::Fails("no hands")),
+            quote!(
+                const EMPTY: &str = "";
+            ),
+        )
+        .unwrap()
+        .to_string();
+
+        assert!(example.contains("fn part2_empty ()"));
+        assert!(example.contains("example (EMPTY)"));
+        assert!(example.contains(":: part2 (& parsed)"));
+        assert!(example.contains(
+            "(aoc_common :: Fails (\"no hands\")) . assert (answer)"
+        ));
+    }
+
+    #[test]
+    fn argument_errors() {
+        let item = quote!(
+            fn part1(input: &str) -> u64 {
+                0
+            }
+        );
+
+        assert_eq!(
+            "missing `day`, e.g. `#[aoc(day = 7, part = 1)]`",
+            aoc_error(quote!(part = 1), item.clone())
+        );
+        assert_eq!(
+            "`day` must be a number from 1 to 25",
+            aoc_error(quote!(day = 26, part = 1), item.clone())
+        );
+        assert_eq!(
+            "`part` must be a number from 1 to 2",
+            aoc_error(quote!(day = 7, part = "one"), item.clone())
+        );
+        assert_eq!(
+            "unknown argument, expected `day`, `part`, `parse` or `generator`",
+            aoc_error(quote!(day = 7, part = 1, year = 2023), item.clone())
+        );
+        assert_eq!(
+            "`generator` is given with part 2, which registers the day",
+            aoc_error(quote!(day = 7, part = 1, generator = g), item.clone())
+        );
+        assert_eq!(
+            "`part` can't be given with `parse`",
+            aoc_error(quote!(day = 7, part = 1, parse), item.clone())
+        );
+        assert_eq!(
+            "`parse` doesn't take a value",
+            aoc_error(quote!(day = 7, parse = hands), item.clone())
+        );
+        assert_eq!(
+            "`part` needs a value, e.g. `#[aoc(day = 7, part = 1)]`",
+            aoc_error(quote!(day = 7, part), item.clone())
+        );
+        assert_eq!(
+            "`day` is given more than once",
+            aoc_error(quote!(day = 7, day = 8, part = 1), item.clone())
+        );
+        assert!(aoc_error(quote!(day 7), item)
+            .ends_with("e.g. `#[aoc(day = 7, part = 1)]`"));
+
+        assert_eq!(
+            "missing `expected`, e.g. \
+             `#[aoc_example(part = 1, expected = 6440)]`",
+            example_error(
+                quote!(part = 1),
+                quote!(
+                    const EXAMPLE: &str = "";
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn item_errors() {
+        let args = quote!(day = 7, part = 1);
+
+        assert_eq!(
+            "`#[aoc]` can only be used on a function",
+            aoc_error(
+                args.clone(),
+                quote!(
+                    const PART1: u64 = 0;
+                )
+            )
+        );
+        assert_eq!(
+            "a part must take a reference to its input",
+            aoc_error(
+                args.clone(),
+                quote!(
+                    fn part1(hands: Vec<Hand>) -> u64 {
+                        0
+                    }
+                )
+            )
+        );
+        assert_eq!(
+            "a part must take its input as `&str`, or a reference to what \
+             the day's `parse` function gives",
+            aoc_error(
+                args.clone(),
+                quote!(
+                    fn part1() -> u64 {
+                        0
+                    }
+                )
+            )
+        );
+        assert_eq!(
+            "a parser must take its input as `&str`",
+            aoc_error(
+                quote!(day = 7, parse),
+                quote!(
+                    fn hands(input: &[u8]) -> Vec<Hand> {
+                        todo!()
+                    }
+                )
+            )
+        );
+        assert_eq!(
+            "a parser must return what it parses",
+            aoc_error(
+                quote!(day = 7, parse),
+                quote!(
+                    fn hands(input: &str) {}
+                )
+            )
+        );
+        assert_eq!(
+            "a part must return its answer",
+            aoc_error(
+                args,
+                quote!(
+                    fn part1(input: &str) {}
+                )
+            )
+        );
+
+        assert_eq!(
+            "`#[aoc_example]` can only be used on a `const` or `static`",
+            example_error(
+                quote!(part = 1, expected = 6440),
+                quote!(
+                    fn example() {}
+                )
+            )
+        );
+    }
+}
@@ -0,0 +1,440 @@
+// Advent of Code runner
+// Liam Fenneman
+
+use std::{collections::BTreeMap, time::Duration};
+
+use anyhow::Context;
+use aoc_common::{
+    answers::{self, Answers, Status},
+    bench,
+    client::{Client, Verdict, WrongAnswers},
+    generate,
+    leaderboard::{Leaderboard, Stats},
+    parallel,
+    puzzle::{Case, Puzzle},
+    report::{self, Format},
+    scaffold, watch, Day, Year,
+};
+
+/// Every year that can be run, in order. A new year is a crate like `2023`
+/// that is added to the workspace and listed here.
+static YEARS: &[&Year] =
+    &[&advent_of_code_2022::YEAR, &advent_of_code_2023::YEAR];
+
+const USAGE: &str = "\
+usage: aoc run <year> <day> [--input <path> | --generated <size>] [--bench]
+               [--format <format>]
+       aoc run <year> --all [--generated <size>] [--bench] [--format <format>]
+               [--timeout <secs>]
+       aoc verify [<year>]
+       aoc new <year> <day> [--example <path> | --puzzle <path>]
+       aoc fetch <year> <day>
+       aoc submit <year> <day> <part> [<answer>] [--input <path>]
+       aoc watch <year> <day> [--interval <ms>]
+       aoc stats <leaderboard.json> [--csv <path>] [--solve]
+               [--timeout <secs>]
+
+options:
+    --input <path>      read the input from a file instead, or stdin with `-`
+    --bench             time each phase over many runs instead
+    --iterations <n>    timed runs when benchmarking (default 10)
+    --warmup <n>        untimed runs before benchmarking (default 3)
+    --generated <size>  use an input made up by the day's generator instead
+    --seed <n>          seed of the generated input (default 2023)
+    --format <format>   print results as `text` (default) or `json`
+    --timeout <secs>    time limit for each day with --all or --solve
+                        (default 60)
+    --example <path>    example input to embed in a new day
+    --puzzle <path>     saved puzzle page to take a new day's examples from
+    --interval <ms>     how often watch looks for changes (default 500)
+    --csv <path>        also write the leaderboard's star times as CSV
+    --solve             also solve the year's days to compare with star times
+
+fetch and submit log in with the session cookie in AOC_SESSION, and identify
+themselves with AOC_USER_AGENT, which should say how to contact you. submit
+solves the day for its answer if none is given.";
+
+fn parse_year(year: &str) -> anyhow::Result<&'static Year> {
+    let year = year
+        .parse::<u16>()
+        .with_context(|| format!("invalid year `{year}`"))?;
+
+    match YEARS.iter().find(|y| y.year == year) {
+        Some(year) => Ok(year),
+        None => anyhow::bail!("{year} is not part of the workspace"),
+    }
+}
+
+fn parse_day(day: &str) -> anyhow::Result<u8> {
+    day.parse().with_context(|| format!("invalid day `{day}`"))
+}
+
+fn run(
+    day: &Day,
+    input: &str,
+    bench: Option<&bench::Options>,
+) -> anyhow::Result<()> {
+    println!("{} day {}", day.year, day.day);
+
+    match bench {
+        Some(options) => day.run_bench(input, options),
+        None => day.run(input),
+    }
+}
+
+fn run_one(
+    year: &str,
+    day: &str,
+    bench: Option<&bench::Options>,
+    generated: Option<&generate::Options>,
+    format: Format,
+) -> anyhow::Result<()> {
+    let year = parse_year(year)?;
+    let day = parse_day(day)?;
+
+    let Some(day) = year.find(day) else {
+        anyhow::bail!("{} day {day} is not registered", year.year);
+    };
+
+    if format == Format::Json {
+        return day.run_json(day.input(generated));
+    }
+
+    run(day, &day.input(generated)?, bench)
+}
+
+fn run_all(
+    year: &str,
+    bench: Option<&bench::Options>,
+    generated: Option<&generate::Options>,
+    format: Format,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let year = parse_year(year)?;
+
+    // every day's input file, unless they are all generated
+    let input = |day: &Day| match generated {
+        Some(options) => day.generated(options),
+        None => aoc_common::read_file(aoc_common::input_path(day)),
+    };
+
+    // benchmarks run one day at a time so they don't skew each other
+    if let Some(options) = bench {
+        let mut failures = 0;
+
+        for day in year.days {
+            let result =
+                input(day).and_then(|input| run(day, &input, Some(options)));
+
+            if let Err(e) = result {
+                eprintln!("{} day {}: {e:#}", day.year, day.day);
+                failures += 1;
+            }
+        }
+
+        if failures > 0 {
+            anyhow::bail!("{failures} of {} days failed", year.days.len());
+        }
+
+        return Ok(());
+    }
+
+    let summary = parallel::solve_all(year.days, input, timeout);
+    let failures = summary.failures();
+
+    match format {
+        Format::Text => print!("{summary}"),
+        Format::Json => {
+            let records = summary
+                .0
+                .into_iter()
+                .flat_map(|(day, outcome)| {
+                    report::records(day, outcome.into_run())
+                })
+                .collect::<Vec<_>>();
+
+            report::print_json(&records)?;
+        }
+    }
+
+    if failures > 0 {
+        anyhow::bail!("{failures} of {} days failed", year.days.len());
+    }
+
+    Ok(())
+}
+
+fn verify(year: Option<&str>) -> anyhow::Result<()> {
+    let years = match year {
+        Some(year) => vec![parse_year(year)?],
+        None => YEARS.to_vec(),
+    };
+    let mut failures = 0;
+
+    for year in years {
+        failures += verify_year(year)?;
+    }
+
+    if failures > 0 {
+        anyhow::bail!("verification failed ({failures} failures)");
+    }
+
+    Ok(())
+}
+
+/// Check every day of the year against its answers, giving the number of
+/// failures.
+fn verify_year(year: &Year) -> anyhow::Result<usize> {
+    let answers = Answers::load(year.root.as_ref())?;
+    let mut failures = 0;
+
+    for day in year.days {
+        let path = aoc_common::input_path(day);
+        if !path.exists() {
+            println!("{} day {}: skipped (no input)", day.year, day.day);
+            continue;
+        }
+
+        let checks = aoc_common::read_file(path)
+            .and_then(|input| answers::check(day, &input, &answers));
+
+        match checks {
+            Ok(checks) => {
+                for check in checks {
+                    println!(
+                        "{} day {} part {}: {check}",
+                        day.year, day.day, check.part
+                    );
+
+                    if matches!(check.status, Status::Wrong { .. }) {
+                        failures += 1;
+                    }
+                }
+            }
+            Err(e) => {
+                println!("{} day {}: FAILED ({e:#})", day.year, day.day);
+                failures += 1;
+            }
+        }
+    }
+
+    Ok(failures)
+}
+
+fn new(
+    year: &str,
+    day: &str,
+    example: Option<&str>,
+    puzzle: Option<&str>,
+) -> anyhow::Result<()> {
+    let year = parse_year(year)?;
+    let day = parse_day(day)?;
+
+    let cases = match (example, puzzle) {
+        (Some(_), Some(_)) => {
+            anyhow::bail!("`--example` can't be used with `--puzzle`")
+        }
+        (Some(path), None) => {
+            Case::unanswered(&aoc_common::read_file(path.into())?).to_vec()
+        }
+        (None, Some(path)) => Puzzle::load(path.as_ref())?.cases(),
+        (None, None) => Vec::new(),
+    };
+
+    let path = scaffold::new_day(
+        year.root.as_ref(),
+        &aoc_common::input_dir(year.root.as_ref()),
+        year.year,
+        day,
+        &cases,
+    )?;
+    println!("created {} day {day} at {}", year.year, path.display());
+
+    Ok(())
+}
+
+fn fetch(year: &str, day: &str) -> anyhow::Result<()> {
+    let year = parse_year(year)?;
+    let day = parse_day(day)?;
+    let path =
+        aoc_common::input_dir(year.root.as_ref()).join(format!("day{day}.txt"));
+
+    if Client::from_env()?.download_input(year.year, day, &path)? {
+        println!("saved {} day {day} input to {}", year.year, path.display());
+    } else {
+        println!(
+            "already have {} day {day} input at {}",
+            year.year,
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// The answer given to `submit` among the arguments after its part, if any.
+///
+/// `--input` is read later by [`aoc_common::read_input`] with
+/// [`aoc_common::flag_value`], so both of its forms are skipped here.
+fn submit_answer<'a>(args: &[&'a str]) -> anyhow::Result<Option<&'a str>> {
+    let mut args = args.iter();
+    let mut answer = None;
+
+    while let Some(&arg) = args.next() {
+        if arg == "--input" {
+            args.next().context("`--input` requires a value")?;
+        } else if arg.starts_with("--input=") {
+            continue;
+        } else if arg.starts_with("--") {
+            anyhow::bail!("`{arg}` is not an answer");
+        } else if answer.replace(arg).is_some() {
+            anyhow::bail!(USAGE);
+        }
+    }
+
+    Ok(answer)
+}
+
+fn submit(
+    year: &str,
+    day: &str,
+    part: &str,
+    answer: Option<&str>,
+) -> anyhow::Result<()> {
+    let year = parse_year(year)?;
+    let day = parse_day(day)?;
+    let part = match part {
+        "1" => 1,
+        "2" => 2,
+        _ => anyhow::bail!("invalid part `{part}`, expected 1 or 2"),
+    };
+
+    let answer = match answer {
+        Some(answer) => answer.to_owned(),
+        None => {
+            let Some(day) = year.find(day) else {
+                anyhow::bail!("{} day {day} is not registered", year.year);
+            };
+
+            let run = (day.solve)(&aoc_common::read_input(day)?)?;
+            let answer = if part == 1 { run.part1 } else { run.part2 };
+            answer?.value
+        }
+    };
+
+    let client = Client::from_env()?;
+    let mut wrong = WrongAnswers::load(WrongAnswers::path(year.root.as_ref()))?;
+
+    let verdict = client.submit(&mut wrong, year.year, day, part, &answer)?;
+    println!("{} day {day} part {part}: {answer}: {verdict}", year.year);
+
+    match verdict {
+        Verdict::Right | Verdict::AlreadySolved => Ok(()),
+        _ => anyhow::bail!("{answer} was not accepted"),
+    }
+}
+
+fn stats(
+    path: &str,
+    csv: Option<&str>,
+    solve: bool,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let leaderboard = Leaderboard::load(path.as_ref())?;
+    let year = leaderboard.year()?;
+
+    // the standings don't need the year to be in the workspace, only the
+    // solve times do
+    let solve_times = match YEARS.iter().find(|y| y.year == year) {
+        Some(year) if solve => {
+            // days without an input are left without a solve time
+            let summary = parallel::solve_all(
+                year.days,
+                |day| aoc_common::read_file(aoc_common::input_path(day)),
+                timeout,
+            );
+            summary
+                .0
+                .iter()
+                .filter(|(_, outcome)| outcome.passed())
+                .filter_map(|(day, outcome)| Some((day.day, outcome.time()?)))
+                .collect()
+        }
+        None if solve => {
+            eprintln!("{year} is not part of the workspace, so isn't solved");
+            BTreeMap::new()
+        }
+        _ => BTreeMap::new(),
+    };
+
+    let stats = Stats {
+        leaderboard: &leaderboard,
+        solve_times,
+    };
+    print!("{stats}");
+
+    if let Some(csv) = csv {
+        std::fs::write(csv, stats.csv())
+            .with_context(|| format!("could not write `{csv}`"))?;
+    }
+
+    Ok(())
+}
+
+fn watch(year: &str, day: &str, interval: Duration) -> anyhow::Result<()> {
+    let year = parse_year(year)?;
+    let day = parse_day(day)?;
+
+    let Some(day) = year.find(day) else {
+        anyhow::bail!("{} day {day} is not registered", year.year);
+    };
+
+    watch::watch(day, interval)
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let bench = bench::Options::from_args(&args)?;
+    let bench = bench.as_ref();
+    let generated = generate::Options::from_args(&args)?;
+    let generated = generated.as_ref();
+    let example = aoc_common::flag_value(&args, "--example")?;
+    let puzzle = aoc_common::flag_value(&args, "--puzzle")?;
+    let input = aoc_common::flag_value(&args, "--input")?;
+    let csv = aoc_common::flag_value(&args, "--csv")?;
+    let solve = args.iter().any(|a| a == "--solve");
+    let format = Format::from_args(&args)?;
+
+    let timeout = aoc_common::parse_flag(&args, "--timeout")?
+        .map(Duration::from_secs)
+        .unwrap_or(parallel::DEFAULT_TIMEOUT);
+
+    let interval = aoc_common::parse_flag(&args, "--interval")?
+        .map(Duration::from_millis)
+        .unwrap_or(watch::DEFAULT_INTERVAL);
+
+    if bench.is_some() && format == Format::Json {
+        anyhow::bail!("`--bench` can't be used with `--format json`");
+    }
+
+    let command = args.iter().map(String::as_str).collect::<Vec<_>>();
+    match command.as_slice() {
+        ["run", _, "--all", ..] if input.is_some() => {
+            anyhow::bail!("`--input` can't be used with `--all`")
+        }
+        ["run", year, "--all", ..] => {
+            run_all(year, bench, generated, format, timeout)
+        }
+        ["run", year, day, ..] => run_one(year, day, bench, generated, format),
+        ["verify"] => verify(None),
+        ["verify", year] => verify(Some(year)),
+        ["new", year, day, ..] => new(year, day, example, puzzle),
+        ["fetch", year, day] => fetch(year, day),
+        ["watch", year, day, ..] => watch(year, day, interval),
+        ["stats", path, ..] => stats(path, csv, solve, timeout),
+        ["submit", year, day, part, rest @ ..] => {
+            submit(year, day, part, submit_answer(rest)?)
+        }
+        _ => anyhow::bail!(USAGE),
+    }
+}